@@ -0,0 +1,419 @@
+use anyhow::Result;
+
+use super::*;
+
+/// A gate in a Bristol Fashion circuit, referencing wires by index.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gate {
+    And {
+        a: usize,
+        b: usize,
+        out: usize,
+    },
+    Xor {
+        a: usize,
+        b: usize,
+        out: usize,
+    },
+    Inv {
+        a: usize,
+        out: usize,
+    },
+    /// Copy wire `a` to `out`.
+    Eqw {
+        a: usize,
+        out: usize,
+    },
+    /// Assign a constant to `out`.
+    Eq {
+        value: bool,
+        out: usize,
+    },
+}
+
+/// A boolean circuit in Bristol Fashion.
+///
+/// https://nigelsmart.github.io/MPC-Circuits/
+///
+/// Input values occupy the first wires and output values the last wires. Gates are listed in
+/// evaluation order. The supported gates are `AND`, `XOR`, `INV` (or `NOT`), `EQ`, `EQW` and
+/// `MAND`, which is expanded into `AND` gates when parsing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BristolCircuit {
+    num_wires: usize,
+    /// Bit length of each input value.
+    inputs: Vec<usize>,
+    /// Bit length of each output value.
+    outputs: Vec<usize>,
+    gates: Vec<Gate>,
+}
+
+impl BristolCircuit {
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut lines = source
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .filter(|line| !line.is_empty());
+        let mut header = || {
+            lines
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Bristol circuit header is truncated"))
+        };
+        let counts = parse_usizes(&header()?)?;
+        let [num_gates, num_wires] = counts[..] else {
+            anyhow::bail!("Bristol circuit header should contain gate and wire counts");
+        };
+        let inputs = parse_value_lengths(&header()?)?;
+        let outputs = parse_value_lengths(&header()?)?;
+
+        let mut gates = Vec::with_capacity(num_gates);
+        let mut num_lines = 0;
+        for (i, line) in lines.enumerate() {
+            num_lines += 1;
+            let Some((op, args)) = line.split_last() else {
+                unreachable!("empty lines are filtered");
+            };
+            let args = parse_usizes(args)
+                .map_err(|e| anyhow::anyhow!("Bristol gate {} is malformed, {e}", i))?;
+            let (&[nin, nout], wires) = args.split_at(2.min(args.len())) else {
+                anyhow::bail!("Bristol gate {} is missing input and output counts", i);
+            };
+            if wires.len() != nin + nout {
+                anyhow::bail!(
+                    "Bristol gate {} lists {} wires, expected {}",
+                    i,
+                    wires.len(),
+                    nin + nout
+                );
+            }
+            if let Some(w) = wires.iter().find(|w| **w >= num_wires) {
+                anyhow::bail!("Bristol gate {} references wire {} of {}", i, w, num_wires);
+            }
+            match (*op, nin, nout) {
+                ("AND", 2, 1) => gates.push(Gate::And {
+                    a: wires[0],
+                    b: wires[1],
+                    out: wires[2],
+                }),
+                ("XOR", 2, 1) => gates.push(Gate::Xor {
+                    a: wires[0],
+                    b: wires[1],
+                    out: wires[2],
+                }),
+                ("INV" | "NOT", 1, 1) => gates.push(Gate::Inv {
+                    a: wires[0],
+                    out: wires[1],
+                }),
+                ("EQW", 1, 1) => gates.push(Gate::Eqw {
+                    a: wires[0],
+                    out: wires[1],
+                }),
+                // the input of an EQ gate is the constant itself, not a wire
+                ("EQ", 1, 1) => {
+                    if wires[0] > 1 {
+                        anyhow::bail!(
+                            "Bristol gate {} assigns non boolean constant {}",
+                            i,
+                            wires[0]
+                        );
+                    }
+                    gates.push(Gate::Eq {
+                        value: wires[0] == 1,
+                        out: wires[1],
+                    })
+                }
+                ("MAND", _, _) if nin == 2 * nout => {
+                    for k in 0..nout {
+                        gates.push(Gate::And {
+                            a: wires[k],
+                            b: wires[nout + k],
+                            out: wires[nin + k],
+                        });
+                    }
+                }
+                _ => anyhow::bail!(
+                    "Bristol gate {} has unsupported operation {} with {} inputs and {} outputs",
+                    i,
+                    op,
+                    nin,
+                    nout
+                ),
+            }
+        }
+
+        // MAND gates expand into several gates, so count lines rather than gates
+        if num_lines != num_gates {
+            anyhow::bail!(
+                "Bristol circuit declares {} gates but lists {}",
+                num_gates,
+                num_lines
+            );
+        }
+
+        let circuit = Self {
+            num_wires,
+            inputs,
+            outputs,
+            gates,
+        };
+        if circuit.num_input_bits() + circuit.num_output_bits() > num_wires {
+            anyhow::bail!(
+                "Bristol circuit has {} input and {} output bits but only {} wires",
+                circuit.num_input_bits(),
+                circuit.num_output_bits(),
+                num_wires
+            );
+        }
+        Ok(circuit)
+    }
+
+    pub fn num_wires(&self) -> usize {
+        self.num_wires
+    }
+
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    pub fn num_input_bits(&self) -> usize {
+        self.inputs.iter().sum()
+    }
+
+    pub fn num_output_bits(&self) -> usize {
+        self.outputs.iter().sum()
+    }
+
+    /// Evaluate the circuit on input bits, returning the output bits.
+    pub fn evaluate(&self, inputs: &[bool]) -> Result<Vec<bool>> {
+        let mut cs = ConstraintSystem::<BinaryScalar>::new();
+        let outputs = self.synthesize(&mut cs, inputs)?;
+        Ok(outputs
+            .iter()
+            .map(|v| !cs.value(&LinearCombination::variable(*v)).is_zero())
+            .collect())
+    }
+
+    /// The R1CS for the circuit. Witnesses are produced by `witness`.
+    pub fn to_r1cs<E: Element>(&self) -> Result<R1CS<E>> {
+        let mut cs = ConstraintSystem::new();
+        self.synthesize(&mut cs, &vec![false; self.num_input_bits()])?;
+        Ok(cs.to_r1cs())
+    }
+
+    /// A satisfying witness of `to_r1cs` for the given input bits.
+    pub fn witness<E: Element>(&self, inputs: &[bool]) -> Result<Vector<E>> {
+        let mut cs = ConstraintSystem::new();
+        self.synthesize(&mut cs, inputs)?;
+        Ok(cs.witness())
+    }
+
+    /// Write the circuit into a constraint system, returning the variables holding the output
+    /// bits. Input bits are allocated first, followed by gate outputs and finally the outputs.
+    ///
+    /// Over `BinaryScalar` XOR and INV gates are linear and only AND gates allocate a variable.
+    /// Over larger prime fields XOR is computed as `a + b - 2ab`, so XOR gates allocate a variable
+    /// as well, and the inputs are constrained to be boolean. Gate outputs are then boolean by
+    /// construction.
+    ///
+    /// `to_r1cs` builds dense matrices with a row per constraint and a column per variable, so
+    /// it only suits small circuits. Larger circuits can be checked in the constraint system with
+    /// `ConstraintSystem::assert_satisfied`.
+    pub fn synthesize<E: Element>(
+        &self,
+        cs: &mut ConstraintSystem<E>,
+        inputs: &[bool],
+    ) -> Result<Vec<usize>> {
+        if inputs.len() != self.num_input_bits() {
+            anyhow::bail!(
+                "Bristol circuit expects {} input bits, got {}",
+                self.num_input_bits(),
+                inputs.len()
+            );
+        }
+        let binary = E::CARDINALITY == 2;
+        let mut wires: Vec<Option<LinearCombination<E>>> = vec![None; self.num_wires];
+        for (wire, bit) in wires.iter_mut().zip(inputs) {
+            let v = LinearCombination::variable(cs.alloc(E::from(*bit as u128)));
+            if !binary {
+                cs.enforce(v.clone(), v.clone() - &cs.one(), LinearCombination::zero());
+            }
+            *wire = Some(v);
+        }
+
+        for (i, gate) in self.gates.iter().enumerate() {
+            let read = |w: usize| {
+                wires[w].clone().ok_or_else(|| {
+                    anyhow::anyhow!("Bristol gate {} reads unassigned wire {}", i, w)
+                })
+            };
+            let (out, value) = match gate {
+                Gate::And { a, b, out } => {
+                    let (a, b) = (read(*a)?, read(*b)?);
+                    let v = cs.alloc(cs.value(&a) * cs.value(&b));
+                    let v = LinearCombination::variable(v);
+                    cs.enforce(a, b, v.clone());
+                    (out, v)
+                }
+                Gate::Xor { a, b, out } if binary => (out, read(*a)? + &read(*b)?),
+                Gate::Xor { a, b, out } => {
+                    let (a, b) = (read(*a)?, read(*b)?);
+                    let (a_val, b_val) = (cs.value(&a), cs.value(&b));
+                    let v = cs.alloc(a_val + b_val - E::from(2) * a_val * b_val);
+                    let v = LinearCombination::variable(v);
+                    cs.enforce(a.clone() * E::from(2), b.clone(), a + &b - &v);
+                    (out, v)
+                }
+                Gate::Inv { a, out } => (out, cs.one() - &read(*a)?),
+                Gate::Eqw { a, out } => (out, read(*a)?),
+                Gate::Eq { value, out } => (out, cs.one() * E::from(*value as u128)),
+            };
+            wires[*out] = Some(value);
+        }
+
+        let mut outputs = Vec::with_capacity(self.num_output_bits());
+        let first_output = self.num_wires - self.num_output_bits();
+        for (w, lc) in wires.into_iter().enumerate().skip(first_output) {
+            let lc =
+                lc.ok_or_else(|| anyhow::anyhow!("Bristol output wire {} is unassigned", w))?;
+            let v = cs.alloc(cs.value(&lc));
+            cs.enforce(lc, cs.one(), LinearCombination::variable(v));
+            outputs.push(v);
+        }
+        Ok(outputs)
+    }
+}
+
+fn parse_usizes(parts: &[&str]) -> Result<Vec<usize>> {
+    parts
+        .iter()
+        .map(|p| {
+            p.parse::<usize>()
+                .map_err(|e| anyhow::anyhow!("expected integer, got {}: {e}", p))
+        })
+        .collect()
+}
+
+/// Parse a line of the form `count len_1 ... len_count`.
+fn parse_value_lengths(parts: &[&str]) -> Result<Vec<usize>> {
+    let values = parse_usizes(parts)?;
+    let Some((count, lengths)) = values.split_first() else {
+        unreachable!("empty lines are filtered");
+    };
+    if lengths.len() != *count {
+        anyhow::bail!(
+            "Bristol circuit declares {} values but lists {} lengths",
+            count,
+            lengths.len()
+        );
+    }
+    Ok(lengths.to_vec())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Add two 2 bit values into a 3 bit sum, exercising every gate type.
+    const ADDER: &str = "12 16
+2 2 2
+1 3
+
+2 1 0 2 4 XOR
+2 1 0 2 5 AND
+2 1 1 3 6 XOR
+2 1 6 5 7 XOR
+4 2 1 6 3 5 8 9 MAND
+2 1 8 9 10 XOR
+1 1 4 11 INV
+1 1 11 12 INV
+1 1 0 15 EQ
+1 1 12 13 EQW
+1 1 7 14 EQW
+1 1 10 15 EQW
+";
+
+    fn bits(value: usize, len: usize) -> Vec<bool> {
+        (0..len).map(|i| (value >> i) & 1 == 1).collect()
+    }
+
+    #[test]
+    fn bristol_evaluate() -> Result<()> {
+        let circuit = BristolCircuit::parse(ADDER)?;
+        assert_eq!(circuit.num_input_bits(), 4);
+        assert_eq!(circuit.num_output_bits(), 3);
+        assert_eq!(circuit.gates().len(), 13);
+        for a in 0..4 {
+            for b in 0..4 {
+                let inputs = [bits(a, 2), bits(b, 2)].concat();
+                assert_eq!(circuit.evaluate(&inputs)?, bits(a + b, 3));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bristol_binary_r1cs() -> Result<()> {
+        let circuit = BristolCircuit::parse(ADDER)?;
+        let r1cs = circuit.to_r1cs::<BinaryScalar>()?;
+        // 3 AND gates and 3 outputs
+        assert_eq!(r1cs.dimension(), (6, 11));
+        for a in 0..4 {
+            for b in 0..4 {
+                let inputs = [bits(a, 2), bits(b, 2)].concat();
+                let mut witness = circuit.witness::<BinaryScalar>(&inputs)?;
+                assert!(r1cs.eval(&witness)?.is_zero());
+                witness[10] += BinaryScalar::one();
+                assert!(!r1cs.eval(&witness)?.is_zero());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bristol_prime_r1cs() -> Result<()> {
+        let circuit = BristolCircuit::parse(ADDER)?;
+        let r1cs = circuit.to_r1cs::<OxfoiScalar>()?;
+        // 4 boolean inputs, 3 AND gates, 4 XOR gates and 3 outputs
+        assert_eq!(r1cs.dimension(), (14, 15));
+        for a in 0..4 {
+            for b in 0..4 {
+                let inputs = [bits(a, 2), bits(b, 2)].concat();
+                let witness = circuit.witness::<OxfoiScalar>(&inputs)?;
+                assert!(r1cs.eval(&witness)?.is_zero());
+                let sum = witness[12..15]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (Into::<u128>::into(*v) as usize) << i)
+                    .sum::<usize>();
+                assert_eq!(sum, a + b);
+            }
+        }
+
+        // a non boolean input is rejected even if the gates are computed consistently
+        let mut witness = circuit.witness::<SevenScalar>(&[false; 4])?;
+        assert!(circuit.to_r1cs::<SevenScalar>()?.eval(&witness)?.is_zero());
+        witness[1] = SevenScalar::from(2u128);
+        assert!(!circuit.to_r1cs::<SevenScalar>()?.eval(&witness)?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn bristol_parse_errors() {
+        assert!(BristolCircuit::parse("").is_err());
+        assert!(BristolCircuit::parse("1 3\n1 1\n1 1\n1 1 0 2 FOO").is_err());
+        assert!(BristolCircuit::parse("1 3\n1 1\n1 1\n2 1 0 1 2 AND").is_ok());
+        assert!(BristolCircuit::parse("1 3\n1 1\n1 1\n2 1 0 1 5 AND").is_err());
+        assert!(BristolCircuit::parse("1 3\n1 1\n1 1\n2 1 0 2 AND").is_err());
+        assert!(BristolCircuit::parse("1 3\n2 1\n1 1\n2 1 0 1 2 AND").is_err());
+        // gate count does not match the gate lines
+        assert!(BristolCircuit::parse("2 3\n1 1\n1 1\n2 1 0 1 2 AND").is_err());
+        assert!(BristolCircuit::parse("0 3\n1 1\n1 1\n2 1 0 1 2 AND").is_err());
+        // EQ constants are bits
+        assert!(BristolCircuit::parse("1 3\n1 1\n1 1\n1 1 1 2 EQ").is_ok());
+        assert!(BristolCircuit::parse("1 3\n1 1\n1 1\n1 1 2 2 EQ").is_err());
+        // wire 1 is read before it is assigned
+        let circuit = BristolCircuit::parse("1 4\n1 1\n1 1\n2 1 0 1 3 AND").unwrap();
+        assert!(circuit.evaluate(&[true]).is_err());
+        assert!(circuit.evaluate(&[true, false]).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+use anyhow::Result;
+
+use super::*;
+
+/// A customizable constraint system, `sum_i c_i * ∘_{j in S_i} (M_j * z) = 0`, for matrices `M_j`,
+/// multisets of matrix indices `S_i` and coefficients `c_i`.
+///
+/// https://eprint.iacr.org/2023/552.pdf
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Debug)]
+pub struct CCS<E: Element> {
+    matrices: Vec<Matrix<E>>,
+    multisets: Vec<Vec<usize>>,
+    coefficients: Vec<E>,
+}
+
+impl<E: Element> From<R1CS<E>> for CCS<E> {
+    /// `(A * z) ∘ (B * z) - C * z` is the CCS with matrices `[A, B, C]`, multisets
+    /// `[{0, 1}, {2}]` and coefficients `[1, -1]`.
+    fn from(r1cs: R1CS<E>) -> Self {
+        Self {
+            matrices: vec![r1cs.a, r1cs.b, r1cs.c],
+            multisets: vec![vec![0, 1], vec![2]],
+            coefficients: vec![E::one(), E::negone()],
+        }
+    }
+}
+
+impl<E: Element> CCS<E> {
+    /// A CCS with no terms over `height` constraints and `width` variables. Every witness
+    /// satisfies it.
+    pub fn identity(height: usize, width: usize) -> Self {
+        Self {
+            matrices: vec![Matrix::zero(height, width)],
+            multisets: Vec::new(),
+            coefficients: Vec::new(),
+        }
+    }
+
+    /// Build a CCS from its parts. Matrix dimensions and multiset indices are checked on
+    /// evaluation.
+    pub fn new(matrices: Vec<Matrix<E>>, multisets: Vec<Vec<usize>>, coefficients: Vec<E>) -> Self {
+        Self {
+            matrices,
+            multisets,
+            coefficients,
+        }
+    }
+
+    /// Add a matrix and return its index.
+    pub fn add_matrix(&mut self, matrix: Matrix<E>) -> usize {
+        self.matrices.push(matrix);
+        self.matrices.len() - 1
+    }
+
+    /// Add the term `coefficient * ∘_{j in multiset} (M_j * z)`.
+    pub fn add_term(&mut self, coefficient: E, multiset: Vec<usize>) {
+        self.coefficients.push(coefficient);
+        self.multisets.push(multiset);
+    }
+
+    pub fn matrices(&self) -> &[Matrix<E>] {
+        &self.matrices
+    }
+
+    pub fn multisets(&self) -> &[Vec<usize>] {
+        &self.multisets
+    }
+
+    pub fn coefficients(&self) -> &[E] {
+        &self.coefficients
+    }
+
+    /// Largest multiset size, the degree of the constraints in the witness.
+    pub fn degree(&self) -> usize {
+        self.multisets.iter().map(|s| s.len()).max().unwrap_or(0)
+    }
+
+    /// Evaluate `sum_i c_i * ∘_{j in S_i} (M_j * z)`. A witness satisfies the system if the
+    /// output is the zero vector.
+    pub fn eval(&self, witness: &Vector<E>) -> Result<Vector<E>> {
+        self.assert_consistency()?;
+        let (height, width) = self.dimension();
+        if witness.len() != width {
+            anyhow::bail!(
+                "CCS witness length mismatch, expected {}, got {}",
+                width,
+                witness.len()
+            );
+        }
+
+        let products = self
+            .matrices
+            .iter()
+            .map(|m| m * witness)
+            .collect::<Vec<_>>();
+        let mut out = Vector::new(height);
+        for (coefficient, multiset) in self.coefficients.iter().zip(&self.multisets) {
+            let mut term = Vector::from(vec![*coefficient; height]);
+            for j in multiset {
+                term *= &products[*j];
+            }
+            out += &term;
+        }
+        Ok(out)
+    }
+
+    /// Check that `witness` satisfies every constraint, reporting the first one that does not.
+    pub fn assert_satisfied(&self, witness: &Vector<E>) -> Result<()> {
+        let out = self.eval(witness)?;
+        if let Some((i, v)) = out.iter().enumerate().find(|(_, v)| !v.is_zero()) {
+            anyhow::bail!("CCS constraint {} not satisfied, evaluates to {}", i, v);
+        }
+        Ok(())
+    }
+
+    /// Number of constraints and variables.
+    pub fn dimension(&self) -> (usize, usize) {
+        self.matrices
+            .first()
+            .map(|m| m.dimension())
+            .unwrap_or((0, 0))
+    }
+
+    fn assert_consistency(&self) -> Result<()> {
+        if self.multisets.len() != self.coefficients.len() {
+            anyhow::bail!(
+                "CCS multiset and coefficient count mismatch, expected {}, got {}",
+                self.multisets.len(),
+                self.coefficients.len()
+            );
+        }
+        let dimension = self.dimension();
+        for (j, m) in self.matrices.iter().enumerate() {
+            if m.dimension() != dimension {
+                anyhow::bail!(
+                    "CCS M_0 and M_{} dimension mismatch, expected {:?}, got {:?}",
+                    j,
+                    dimension,
+                    m.dimension()
+                );
+            }
+        }
+        for (i, multiset) in self.multisets.iter().enumerate() {
+            if let Some(j) = multiset.iter().find(|j| **j >= self.matrices.len()) {
+                anyhow::bail!(
+                    "CCS multiset S_{} references matrix {}, only {} matrices exist",
+                    i,
+                    j,
+                    self.matrices.len()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::multiplication_r1cs;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn ccs_from_r1cs() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness) = multiplication_r1cs::<Field, _>(6, 14, rng);
+        let ccs = CCS::from(r1cs.clone());
+        assert_eq!(ccs.dimension(), r1cs.dimension());
+        assert_eq!(ccs.degree(), 2);
+        assert_eq!(ccs.eval(&witness)?, r1cs.eval(&witness)?);
+        ccs.assert_satisfied(&witness)?;
+
+        let other = Vector::random(14, rng);
+        assert_eq!(ccs.eval(&other)?, r1cs.eval(&other)?);
+        assert!(ccs.assert_satisfied(&other).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn ccs_high_degree() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        // constraint i: z[i]^5 - z[i + 4] = 0, a single degree 5 gate per row
+        let height = 4;
+        let mut ccs = CCS::<Field>::new(Vec::new(), Vec::new(), Vec::new());
+        let mut input = Matrix::zero(height, 2 * height);
+        let mut output = Matrix::zero(height, 2 * height);
+        for i in 0..height {
+            input[i][i] = Field::one();
+            output[i][height + i] = Field::one();
+        }
+        let input = ccs.add_matrix(input);
+        let output = ccs.add_matrix(output);
+        ccs.add_term(Field::one(), vec![input; 5]);
+        ccs.add_term(Field::negone(), vec![output]);
+        assert_eq!(ccs.degree(), 5);
+
+        let mut witness = Vector::<Field>::random(2 * height, rng);
+        for i in 0..height {
+            witness[height + i] = witness[i].pow(5);
+        }
+        ccs.assert_satisfied(&witness)?;
+        witness[height + 2] += Field::one();
+        let err = ccs.assert_satisfied(&witness).unwrap_err();
+        assert!(err.to_string().contains("constraint 2"));
+        Ok(())
+    }
+
+    #[test]
+    fn ccs_inconsistent() {
+        let ccs = CCS::<Field>::new(
+            vec![Matrix::zero(2, 3), Matrix::zero(2, 4)],
+            vec![vec![0, 1]],
+            vec![Field::one()],
+        );
+        assert!(ccs.eval(&Vector::new(3)).is_err());
+
+        let ccs = CCS::<Field>::new(
+            vec![Matrix::zero(2, 3)],
+            vec![vec![0, 1]],
+            vec![Field::one()],
+        );
+        assert!(ccs.eval(&Vector::new(3)).is_err());
+
+        let ccs = CCS::<Field>::new(vec![Matrix::zero(2, 3)], vec![vec![0]], Vec::new());
+        assert!(ccs.eval(&Vector::new(3)).is_err());
+
+        let ccs = CCS::<Field>::identity(2, 3);
+        assert!(ccs.eval(&Vector::new(4)).is_err());
+        assert!(ccs.eval(&Vector::new(3)).is_ok());
+    }
+}
@@ -5,6 +5,21 @@ use anyhow::Result;
 pub trait ElementHasher<E: Element> {
     fn finish(&self) -> E;
     fn write(&mut self, bytes: &[u8]);
+
+    /// Write an element as `ceil(E::BIT_WIDTH / 8)` little endian bytes. The fixed width keeps
+    /// sequences of elements unambiguous.
+    fn write_element(&mut self, element: &E) {
+        let v: u128 = (*element).into();
+        self.write(&v.to_le_bytes()[..E::BIT_WIDTH.div_ceil(8)]);
+    }
+
+    /// Derive a Fiat-Shamir challenge from the data written so far. The challenge is written back
+    /// so consecutive challenges are distinct.
+    fn challenge(&mut self) -> E {
+        let out = self.finish();
+        self.write_element(&out);
+        out
+    }
 }
 
 /// An implementation of Baum et. al. commitments over a scalar field.
@@ -68,12 +83,11 @@ impl<E: Element> BDLOPScalar<E> {
         rng: &mut R,
     ) -> ((Vector<E>, Vector<E>), Self) {
         let (a_1, a_2) = lattice;
-        let msg_len = val.len();
 
         // the secret committing to the zero component
-        let r_1 = Vector::random(a_1.height(), rng);
+        let r_1 = Vector::random(a_1.width(), rng);
         // the secret committing to the message component
-        let r_2 = Vector::random(msg_len, rng);
+        let r_2 = Vector::random(a_2.width(), rng);
 
         let c_1 = &a_1 * &r_1;
         let c_2 = &a_2 * &r_2 + &val;
@@ -116,7 +130,7 @@ mod test {
     #[test]
     fn bdlop_commit_var_dimension() {
         type Field = OxfoiScalar;
-        let rng = &mut rand::rng();
+        let rng = &mut crate::test::test_rng();
         // just make sure our dimensions match in matrix/vector ops
         for i in 1..10 {
             let lattice = BDLOPScalar::lattice_for(i, rng);
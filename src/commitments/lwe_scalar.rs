@@ -2,10 +2,64 @@ use crate::*;
 
 use anyhow::Result;
 
+/// Distribution of the error added to an LWE commitment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorDistribution {
+    /// Uniform over {-1, 0, 1}.
+    UniformTernary,
+    /// Centered binomial with parameter eta, `sum_{i < eta} (a_i - b_i)` for uniform bits
+    /// `a_i, b_i`. Supported on `[-eta, eta]` with variance `eta / 2`.
+    CenteredBinomial(u32),
+    /// Discrete Gaussian with width sigma, sampled in constant time with a `GaussianCDT`.
+    DiscreteGaussian(f64),
+}
+
+impl ShortDistribution for ErrorDistribution {
+    fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> E {
+        match self {
+            Self::UniformTernary => UniformBounded::new(1).sample(rng),
+            Self::CenteredBinomial(eta) => CenteredBinomial::new(*eta).sample(rng),
+            Self::DiscreteGaussian(sigma) => {
+                ShortDistribution::sample(GaussianCDT::new::<E>(*sigma).as_ref(), rng)
+            }
+        }
+    }
+
+    /// Looks up the Gaussian table once for the whole vector.
+    fn sample_vector<E: Element, R: Rng>(&self, len: usize, rng: &mut R) -> Vector<E> {
+        match self {
+            Self::DiscreteGaussian(sigma) => GaussianCDT::new::<E>(*sigma).sample_vector(len, rng),
+            _ => (0..len).map(|_| self.sample(rng)).collect(),
+        }
+    }
+
+    /// For the discrete Gaussian this is the tail cut of the CDT.
+    fn tail_bound(&self) -> u128 {
+        match self {
+            Self::UniformTernary => UniformBounded::new(1).tail_bound(),
+            Self::CenteredBinomial(eta) => CenteredBinomial::new(*eta).tail_bound(),
+            Self::DiscreteGaussian(sigma) => GaussianCDT::tail_cut(*sigma),
+        }
+    }
+
+    fn expected_norm(&self, len: usize) -> f64 {
+        match self {
+            Self::UniformTernary => UniformBounded::new(1).expected_norm(len),
+            Self::CenteredBinomial(eta) => CenteredBinomial::new(*eta).expected_norm(len),
+            Self::DiscreteGaussian(sigma) => (len as f64).sqrt() * sigma,
+        }
+    }
+}
+
+/// Commitments based on the learning with errors problem over a scalar field.
+///
+/// The commitment tracks a bound on the infinity norm of its error through homomorphic
+/// operations, so `try_open_bounded` can check openings without an explicit bound.
 #[derive(Clone, Debug)]
 pub struct LWEScalar<E: Element> {
     lattice: Matrix<E>,
     commitment: Vector<E>,
+    error_bound: u128,
 }
 
 impl<E: Element> LWEScalar<E> {
@@ -15,22 +69,34 @@ impl<E: Element> LWEScalar<E> {
         Matrix::<E>::random(height, element_len, rng)
     }
 
+    /// Commit with uniform ternary error.
     pub fn commit<R: Rng>(val: Vector<E>, lattice: Matrix<E>, rng: &mut R) -> Self {
+        Self::commit_with(val, lattice, &ErrorDistribution::UniformTernary, rng)
+    }
+
+    /// Commit with error sampled from `distribution`.
+    pub fn commit_with<D: ShortDistribution, R: Rng>(
+        val: Vector<E>,
+        lattice: Matrix<E>,
+        distribution: &D,
+        rng: &mut R,
+    ) -> Self {
         let (height, _width) = lattice.dimension();
-        let mut err = Vector::new(height);
-        for i in 0..height {
-            // generate a value between 0 and 2
-            let v = rng.random_range(0..=2);
-            // move it to the range -1..1 in the field
-            err[i] = E::from(v) - E::one();
-        }
+        let err = Vector::sample_from(height, distribution, rng);
         let commitment = &lattice * &val + &err;
         Self {
             lattice,
             commitment,
+            error_bound: distribution.tail_bound(),
         }
     }
 
+    /// Bound on the distance from zero of each error entry, derived from the error distribution
+    /// and the homomorphic operations applied since commitment.
+    pub fn error_bound(&self) -> u128 {
+        self.error_bound
+    }
+
     /// Attempt to open a commitment to a value, with each error less than `max_err` distance from zero. If successful returns the error vector.
     pub fn try_open(&self, val: &Vector<E>, max_err: u128) -> Result<Vector<E>> {
         let maybe_committed_no_err = &self.lattice * val;
@@ -47,6 +113,12 @@ impl<E: Element> LWEScalar<E> {
         }
         Ok(err)
     }
+
+    /// Attempt to open a commitment to a value with the tracked error bound. If successful
+    /// returns the error vector.
+    pub fn try_open_bounded(&self, val: &Vector<E>) -> Result<Vector<E>> {
+        self.try_open(val, self.error_bound)
+    }
 }
 
 impl<E: Element> Sub<&Self> for LWEScalar<E> {
@@ -60,6 +132,7 @@ impl<E: Element> Sub<&Self> for LWEScalar<E> {
 impl<E: Element> SubAssign<&Self> for LWEScalar<E> {
     fn sub_assign(&mut self, rhs: &Self) {
         self.commitment -= &rhs.commitment;
+        self.error_bound = self.error_bound.saturating_add(rhs.error_bound);
     }
 }
 
@@ -74,6 +147,7 @@ impl<E: Element> Add<&Self> for LWEScalar<E> {
 impl<E: Element> AddAssign<&Self> for LWEScalar<E> {
     fn add_assign(&mut self, rhs: &Self) {
         self.commitment += &rhs.commitment;
+        self.error_bound = self.error_bound.saturating_add(rhs.error_bound);
     }
 }
 
@@ -88,6 +162,9 @@ impl<E: Element> Mul<E> for LWEScalar<E> {
 impl<E: Element> MulAssign<E> for LWEScalar<E> {
     fn mul_assign(&mut self, rhs: E) {
         self.commitment *= rhs;
+        self.error_bound = self
+            .error_bound
+            .saturating_mul(rhs.displacement().unsigned_abs());
     }
 }
 
@@ -102,6 +179,7 @@ impl<E: Element> Mul<&Vector<E>> for LWEScalar<E> {
 impl<E: Element> MulAssign<&Vector<E>> for LWEScalar<E> {
     fn mul_assign(&mut self, rhs: &Vector<E>) {
         self.commitment *= rhs;
+        self.error_bound = self.error_bound.saturating_mul(rhs.norm_inf());
     }
 }
 
@@ -112,7 +190,7 @@ mod test {
     #[test]
     fn should_be_additively_homomorphic() -> Result<()> {
         type Field = OxfoiScalar;
-        let rng = &mut rand::rng();
+        let rng = &mut crate::test::test_rng();
 
         let lattice = LWEScalar::lattice_for(1, rng);
 
@@ -138,4 +216,69 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn error_bound_tracking() -> Result<()> {
+        type Field = OxfoiScalar;
+        let rng = &mut crate::test::test_rng();
+
+        let lattice = LWEScalar::lattice_for(1, rng);
+        let a = Field::sample_rand(rng);
+        let b = Field::sample_rand(rng);
+        let comm_a = LWEScalar::commit(a.into(), lattice.clone(), rng);
+        let comm_b = LWEScalar::commit(b.into(), lattice.clone(), rng);
+        assert_eq!(comm_a.error_bound(), 1);
+        comm_a.try_open_bounded(&a.into())?;
+
+        let sum = comm_a.clone() + &comm_b;
+        assert_eq!(sum.error_bound(), 2);
+        sum.try_open_bounded(&(a + b).into())?;
+        let diff = sum - &comm_b;
+        assert_eq!(diff.error_bound(), 3);
+        diff.try_open_bounded(&a.into())?;
+        let scaled = comm_a * Field::negone();
+        assert_eq!(scaled.error_bound(), 1);
+        scaled.try_open_bounded(&(a * Field::negone()).into())?;
+        assert!(scaled.try_open_bounded(&a.into()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn error_distributions() -> Result<()> {
+        type Field = OxfoiScalar;
+        let rng = &mut crate::test::test_rng();
+
+        let lattice = LWEScalar::lattice_for(2, rng);
+        let val = Vector::<Field>::random(2, rng);
+        for (distribution, bound) in [
+            (ErrorDistribution::UniformTernary, 1),
+            (ErrorDistribution::CenteredBinomial(3), 3),
+            (ErrorDistribution::DiscreteGaussian(3.2), 26),
+        ] {
+            assert_eq!(distribution.tail_bound(), bound);
+            let comm = LWEScalar::commit_with(val.clone(), lattice.clone(), &distribution, rng);
+            assert_eq!(comm.error_bound(), bound);
+            let err = comm.try_open_bounded(&val)?;
+            assert!(err.norm_inf() <= bound);
+            assert!(comm.try_open_bounded(&Vector::random(2, rng)).is_err());
+        }
+
+        // sample variance roughly matches the distribution
+        const SAMPLES: usize = 20_000;
+        for (distribution, variance) in [
+            (ErrorDistribution::UniformTernary, 2.0 / 3.0),
+            (ErrorDistribution::CenteredBinomial(4), 2.0),
+            (ErrorDistribution::DiscreteGaussian(2.0), 4.0),
+        ] {
+            let sum_sq = (0..SAMPLES)
+                .map(|_| (distribution.sample::<Field, _>(rng).displacement() as f64).powi(2))
+                .sum::<f64>();
+            let measured = sum_sq / SAMPLES as f64;
+            assert!(
+                (measured - variance).abs() / variance < 0.1,
+                "{distribution:?} variance {measured}, expected {variance}"
+            );
+        }
+        Ok(())
+    }
 }
@@ -1,7 +1,9 @@
 mod bdlop_scalar;
 mod lwe_scalar;
+mod sis_multilinear;
 mod sis_scalar;
 
 pub use bdlop_scalar::*;
 pub use lwe_scalar::*;
+pub use sis_multilinear::*;
 pub use sis_scalar::*;
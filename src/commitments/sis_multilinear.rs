@@ -0,0 +1,193 @@
+use crate::*;
+
+use anyhow::Result;
+
+/// A commitment to a multilinear polynomial that can later be opened at an arbitrary point.
+pub trait MultilinearCommitment<E: Element> {
+    type Commitment: Clone;
+    /// Secret state kept by the committer to produce evaluation proofs.
+    type Opening;
+    type EvaluationProof: Clone;
+
+    /// Number of variables of the polynomials accepted by `commit`.
+    fn num_vars(&self) -> usize;
+
+    fn commit<R: Rng>(
+        &self,
+        polynomial: &MultilinearPolynomial<E>,
+        rng: &mut R,
+    ) -> (Self::Commitment, Self::Opening);
+
+    /// Write a commitment into a Fiat-Shamir transcript.
+    fn write_commitment<H: ElementHasher<E>>(commitment: &Self::Commitment, hasher: &mut H);
+
+    /// Prove that the committed polynomial evaluates to `polynomial.evaluate(point)`.
+    fn open(
+        &self,
+        polynomial: &MultilinearPolynomial<E>,
+        opening: &Self::Opening,
+        point: &[E],
+    ) -> Self::EvaluationProof;
+
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        point: &[E],
+        value: E,
+        proof: &Self::EvaluationProof,
+    ) -> Result<()>;
+}
+
+/// Commit to a multilinear polynomial by committing to the `part_bits` decomposition of its
+/// evaluations with `SISScalar`.
+///
+/// Evaluation proofs reveal the decomposed evaluations and are linear in the size of the
+/// polynomial. This is a transparent stand-in that allows lattice commitments to be used where a
+/// `MultilinearCommitment` is required, it is not hiding.
+#[derive(Clone, Debug)]
+pub struct SISMultilinear<E: Element> {
+    lattice: Matrix<E>,
+    num_vars: usize,
+    part_bits: usize,
+}
+
+impl<E: Element> SISMultilinear<E> {
+    /// Sample a lattice for committing to polynomials in `num_vars` variables with evaluations
+    /// decomposed into `part_bits` chunks.
+    pub fn setup<R: Rng>(num_vars: usize, part_bits: usize, rng: &mut R) -> Self {
+        let msg_len = (1 << num_vars) * E::bits_vec_len(part_bits);
+        Self {
+            lattice: SISScalar::lattice_for(msg_len, rng),
+            num_vars,
+            part_bits,
+        }
+    }
+
+    /// Decompose each evaluation of a polynomial into `part_bits` chunks.
+    fn decompose(&self, polynomial: &MultilinearPolynomial<E>) -> Vector<E> {
+        Vector::concat(
+            polynomial
+                .evaluations()
+                .iter()
+                .map(|v| v.as_le_bits_vec(self.part_bits)),
+        )
+    }
+
+    /// Recompose the evaluations of a polynomial from `part_bits` chunks.
+    fn recompose(&self, parts: &Vector<E>) -> MultilinearPolynomial<E> {
+        let evaluations = parts
+            .chunks(E::bits_vec_len(self.part_bits))
+            .map(|chunk| {
+                let mut out = E::zero();
+                for (i, part) in chunk.iter().enumerate() {
+                    out += *part * E::from(1u128 << (i * self.part_bits));
+                }
+                out
+            })
+            .collect();
+        MultilinearPolynomial::new(evaluations)
+    }
+}
+
+impl<E: Element> MultilinearCommitment<E> for SISMultilinear<E> {
+    type Commitment = Vector<E>;
+    type Opening = Vector<E>;
+    type EvaluationProof = Vector<E>;
+
+    fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    fn commit<R: Rng>(
+        &self,
+        polynomial: &MultilinearPolynomial<E>,
+        _rng: &mut R,
+    ) -> (Self::Commitment, Self::Opening) {
+        assert_eq!(
+            polynomial.num_vars(),
+            self.num_vars,
+            "SISMultilinear::commit polynomial has wrong number of variables"
+        );
+        let parts = self.decompose(polynomial);
+        let commitment = SISScalar::commit(parts.clone(), self.lattice.clone()).commitment;
+        (commitment, parts)
+    }
+
+    fn write_commitment<H: ElementHasher<E>>(commitment: &Self::Commitment, hasher: &mut H) {
+        for v in commitment.iter() {
+            hasher.write_element(v);
+        }
+    }
+
+    fn open(
+        &self,
+        _polynomial: &MultilinearPolynomial<E>,
+        opening: &Self::Opening,
+        _point: &[E],
+    ) -> Self::EvaluationProof {
+        opening.clone()
+    }
+
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        point: &[E],
+        value: E,
+        proof: &Self::EvaluationProof,
+    ) -> Result<()> {
+        let expected_len = (1 << self.num_vars) * E::bits_vec_len(self.part_bits);
+        if proof.len() != expected_len {
+            anyhow::bail!(
+                "SISMultilinear evaluation proof has length {}, expected {}",
+                proof.len(),
+                expected_len
+            );
+        }
+        let recomputed = SISScalar::commit(proof.clone(), self.lattice.clone());
+        recomputed.try_open(proof, (1 << self.part_bits) - 1)?;
+        if recomputed.commitment != *commitment {
+            anyhow::bail!("SISMultilinear evaluation proof does not match commitment");
+        }
+        if self.recompose(proof).evaluate(point) != value {
+            anyhow::bail!("SISMultilinear committed polynomial does not evaluate to value");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sis_multilinear_open() -> Result<()> {
+        type Field = OxfoiScalar;
+        let rng = &mut crate::test::test_rng();
+
+        let num_vars = 3;
+        let pcs = SISMultilinear::<Field>::setup(num_vars, 8, rng);
+        let poly = MultilinearPolynomial::random(num_vars, rng);
+        let (commitment, opening) = pcs.commit(&poly, rng);
+        let point = Vector::<Field>::random(num_vars, rng);
+        let value = poly.evaluate(&point[..]);
+        let proof = pcs.open(&poly, &opening, &point[..]);
+        pcs.verify(&commitment, &point[..], value, &proof)?;
+
+        assert!(
+            pcs.verify(&commitment, &point[..], value + Field::one(), &proof)
+                .is_err()
+        );
+        let other = MultilinearPolynomial::random(num_vars, rng);
+        let (other_commitment, other_opening) = pcs.commit(&other, rng);
+        assert!(
+            pcs.verify(&other_commitment, &point[..], value, &proof)
+                .is_err()
+        );
+        let other_proof = pcs.open(&other, &other_opening, &point[..]);
+        assert!(
+            pcs.verify(&commitment, &point[..], value, &other_proof)
+                .is_err()
+        );
+        Ok(())
+    }
+}
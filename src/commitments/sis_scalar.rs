@@ -23,17 +23,14 @@ impl<E: Element> SISScalar<E> {
         }
     }
 
+    /// The public lattice the commitment was computed with.
+    pub fn lattice(&self) -> &Matrix<E> {
+        &self.lattice
+    }
+
     pub fn try_open(&self, val: &Vector<E>, max_dist: u128) -> Result<()> {
-        for v in val.iter() {
-            let disp = v.displacement();
-            if disp.unsigned_abs() > max_dist {
-                anyhow::bail!(
-                    "Error opening SIS commitment, value contains element {} beyond bound {}",
-                    disp,
-                    max_dist
-                );
-            }
-        }
+        val.assert_norm_inf(max_dist)
+            .map_err(|e| anyhow::anyhow!("Error opening SIS commitment, {e}"))?;
         let expected_commitment = &self.lattice * val;
         if expected_commitment != self.commitment {
             anyhow::bail!("Error opening SIS commitment, commitment mismatch");
@@ -91,7 +88,7 @@ mod test {
     #[test]
     fn should_be_additively_homomorphic() -> Result<()> {
         type Field = OxfoiScalar;
-        let rng = &mut rand::rng();
+        let rng = &mut crate::test::test_rng();
         const PART_BITS: usize = 8;
         // allow a single addition
         const ARITH_MAX: u128 = 1 << (PART_BITS + 1);
@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use super::*;
+
+/// A sparse linear combination of witness variables, `sum_i coefficient_i * z[variable_i]`.
+/// Variable 0 is the constant one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinearCombination<E: Element> {
+    terms: BTreeMap<usize, E>,
+}
+
+impl<E: Element> LinearCombination<E> {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn variable(index: usize) -> Self {
+        Self::zero().add_term(index, E::one())
+    }
+
+    /// The constant `value`, as a multiple of variable 0.
+    pub fn constant(value: E) -> Self {
+        Self::zero().add_term(ConstraintSystem::<E>::ONE, value)
+    }
+
+    /// Add `coefficient * z[index]`.
+    pub fn add_term(mut self, index: usize, coefficient: E) -> Self {
+        let entry = self.terms.entry(index).or_insert(E::zero());
+        *entry += coefficient;
+        if entry.is_zero() {
+            self.terms.remove(&index);
+        }
+        self
+    }
+
+    /// Nonzero terms as `(variable, coefficient)` in increasing variable order.
+    pub fn terms(&self) -> impl Iterator<Item = (usize, E)> + '_ {
+        self.terms.iter().map(|(i, v)| (*i, *v))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn evaluate(&self, witness: &[E]) -> E {
+        self.terms()
+            .fold(E::zero(), |acc, (i, v)| acc + v * witness[i])
+    }
+}
+
+impl<E: Element> Add<&Self> for LinearCombination<E> {
+    type Output = Self;
+    fn add(mut self, rhs: &Self) -> Self::Output {
+        for (i, v) in rhs.terms() {
+            self = self.add_term(i, v);
+        }
+        self
+    }
+}
+
+impl<E: Element> Sub<&Self> for LinearCombination<E> {
+    type Output = Self;
+    fn sub(mut self, rhs: &Self) -> Self::Output {
+        for (i, v) in rhs.terms() {
+            self = self.add_term(i, E::zero() - v);
+        }
+        self
+    }
+}
+
+impl<E: Element> Mul<E> for LinearCombination<E> {
+    type Output = Self;
+    fn mul(mut self, rhs: E) -> Self::Output {
+        if rhs.is_zero() {
+            return Self::zero();
+        }
+        for v in self.terms.values_mut() {
+            *v *= rhs;
+        }
+        self
+    }
+}
+
+/// A constraint `a * b = c` over linear combinations of witness variables.
+#[derive(Clone, Debug)]
+pub struct Constraint<E: Element> {
+    pub a: LinearCombination<E>,
+    pub b: LinearCombination<E>,
+    pub c: LinearCombination<E>,
+}
+
+/// Incrementally builds an R1CS and a witness for it. Variables are allocated with their value so
+/// the witness is generated as the constraints are written.
+#[derive(Clone, Debug)]
+pub struct ConstraintSystem<E: Element> {
+    witness: Vec<E>,
+    constraints: Vec<Constraint<E>>,
+}
+
+impl<E: Element> Default for ConstraintSystem<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Element> ConstraintSystem<E> {
+    /// Index of the variable fixed to one.
+    pub const ONE: usize = 0;
+
+    pub fn new() -> Self {
+        Self {
+            witness: vec![E::one()],
+            constraints: Vec::new(),
+        }
+    }
+
+    /// The constant one as a linear combination.
+    pub fn one(&self) -> LinearCombination<E> {
+        LinearCombination::variable(Self::ONE)
+    }
+
+    /// Allocate a witness variable with `value`, returning its index.
+    pub fn alloc(&mut self, value: E) -> usize {
+        self.witness.push(value);
+        self.witness.len() - 1
+    }
+
+    /// Enforce `a * b = c`.
+    pub fn enforce(
+        &mut self,
+        a: LinearCombination<E>,
+        b: LinearCombination<E>,
+        c: LinearCombination<E>,
+    ) {
+        self.constraints.push(Constraint { a, b, c });
+    }
+
+    /// Value of a linear combination under the current witness.
+    pub fn value(&self, lc: &LinearCombination<E>) -> E {
+        lc.evaluate(&self.witness)
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.witness.len()
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    pub fn constraints(&self) -> &[Constraint<E>] {
+        &self.constraints
+    }
+
+    pub fn witness(&self) -> Vector<E> {
+        self.witness.clone().into()
+    }
+
+    /// Check every constraint against the witness without building dense matrices, reporting
+    /// the first one that does not hold.
+    pub fn assert_satisfied(&self) -> Result<()> {
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            let a = self.value(&constraint.a);
+            let b = self.value(&constraint.b);
+            let c = self.value(&constraint.c);
+            if a * b != c {
+                anyhow::bail!(
+                    "Constraint {} not satisfied, {} * {} does not equal {}",
+                    i,
+                    a,
+                    b,
+                    c
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the dense R1CS. The matrices have one row per constraint and one column per
+    /// variable.
+    pub fn to_r1cs(&self) -> R1CS<E> {
+        let mut r1cs = R1CS::identity(self.num_constraints(), self.num_variables());
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            for (j, v) in constraint.a.terms() {
+                r1cs.a[i][j] = v;
+            }
+            for (j, v) in constraint.b.terms() {
+                r1cs.b[i][j] = v;
+            }
+            for (j, v) in constraint.c.terms() {
+                r1cs.c[i][j] = v;
+            }
+        }
+        r1cs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn linear_combination() {
+        let rng = &mut crate::test::test_rng();
+
+        let witness = Vector::<Field>::random(4, rng);
+        let x = LinearCombination::variable(1) * Field::from(3) + &LinearCombination::variable(2);
+        let y = LinearCombination::variable(2) + &LinearCombination::constant(Field::from(5));
+        assert_eq!(
+            x.evaluate(witness.as_slice()),
+            Field::from(3) * witness[1] + witness[2]
+        );
+        assert_eq!(
+            (x.clone() - &y).evaluate(witness.as_slice()),
+            Field::from(3) * witness[1] - Field::from(5) * witness[0]
+        );
+        // cancelling terms are removed
+        assert_eq!((x.clone() - &x), LinearCombination::zero());
+        assert!((x * Field::zero()).is_zero());
+    }
+
+    #[test]
+    fn constraint_system() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        // x^3 + x + 5 = out
+        let mut cs = ConstraintSystem::<Field>::new();
+        let x_val = Field::sample_rand(rng);
+        let x = cs.alloc(x_val);
+        let x_sq = cs.alloc(x_val * x_val);
+        let out = cs.alloc(x_val * x_val * x_val + x_val + Field::from(5));
+        cs.enforce(
+            LinearCombination::variable(x),
+            LinearCombination::variable(x),
+            LinearCombination::variable(x_sq),
+        );
+        cs.enforce(
+            LinearCombination::variable(x_sq),
+            LinearCombination::variable(x),
+            LinearCombination::variable(out)
+                - &LinearCombination::variable(x)
+                - &(cs.one() * Field::from(5)),
+        );
+        cs.assert_satisfied()?;
+        assert_eq!(cs.num_variables(), 4);
+        assert_eq!(cs.num_constraints(), 2);
+        let r1cs = cs.to_r1cs();
+        assert_eq!(r1cs.dimension(), (2, 4));
+        assert!(r1cs.eval(&cs.witness())?.is_zero());
+
+        cs.alloc(Field::zero());
+        cs.enforce(cs.one(), cs.one(), LinearCombination::zero());
+        let err = cs.assert_satisfied().unwrap_err();
+        assert!(err.to_string().contains("Constraint 2"));
+        Ok(())
+    }
+}
@@ -15,10 +15,6 @@ impl Element for BinaryScalar {
         self.val == 0
     }
 
-    fn sample_rand<R: Rng>(rng: &mut R) -> Self {
-        Self::from(rng.random::<u8>())
-    }
-
     fn as_le_bits_vec(&self, bits: usize) -> Vector<Self> {
         assert_eq!(bits, 1);
         [self.clone()].to_vec().into()
@@ -22,7 +22,7 @@ impl From<BinaryScalar> for OxfoiScalar {
 impl Distribution<OxfoiScalar> for StandardUniform {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> OxfoiScalar {
         OxfoiScalar {
-            val: rng.random::<u128>() % F,
+            val: uniform_below(F, rng),
         }
     }
 }
@@ -46,10 +46,6 @@ impl Element for OxfoiScalar {
     fn is_zero(&self) -> bool {
         self.val == 0u128
     }
-
-    fn sample_rand<R: Rng>(rng: &mut R) -> Self {
-        rng.random()
-    }
 }
 
 impl Display for OxfoiScalar {
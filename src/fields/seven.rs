@@ -21,7 +21,7 @@ impl From<BinaryScalar> for SevenScalar {
 impl Distribution<SevenScalar> for StandardUniform {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SevenScalar {
         SevenScalar {
-            val: rng.random::<u8>() % F,
+            val: uniform_below(F as u128, rng) as u8,
         }
     }
 }
@@ -53,10 +53,6 @@ impl Element for SevenScalar {
     fn is_zero(&self) -> bool {
         self.val == 0
     }
-
-    fn sample_rand<R: Rng>(rng: &mut R) -> Self {
-        rng.random()
-    }
 }
 
 impl Display for SevenScalar {
@@ -0,0 +1,341 @@
+use anyhow::Result;
+
+use super::*;
+
+/// Nova style folding of relaxed R1CS instances, committing to the witness and error vectors with
+/// `SISScalar`.
+///
+/// https://eprint.iacr.org/2021/370.pdf
+///
+/// The folded witness is `z_1 + r * z_2` for a field sized challenge `r`, so folded witnesses are
+/// not short and the commitments are only checked for consistency, not for the SIS norm bound.
+#[derive(Clone, Debug)]
+pub struct FoldingParams<E: Element> {
+    witness_lattice: Matrix<E>,
+    error_lattice: Matrix<E>,
+}
+
+/// The public part of a committed relaxed R1CS instance.
+#[derive(Clone)]
+pub struct FoldedInstance<E: Element> {
+    pub u: E,
+    pub witness_commitment: SISScalar<E>,
+    pub error_commitment: SISScalar<E>,
+}
+
+/// The private part of a committed relaxed R1CS instance.
+#[derive(Clone, Debug)]
+pub struct FoldedWitness<E: Element> {
+    pub witness: Vector<E>,
+    pub error: Vector<E>,
+}
+
+/// A commitment to the cross term `T` sent by the prover when folding two instances.
+#[derive(Clone)]
+pub struct FoldingProof<E: Element> {
+    pub cross_term_commitment: SISScalar<E>,
+}
+
+impl<E: Element> FoldingParams<E> {
+    /// Sample lattices for committing to witnesses and error vectors of `r1cs`.
+    pub fn setup<R: Rng>(r1cs: &R1CS<E>, rng: &mut R) -> Self {
+        let (height, width) = r1cs.dimension();
+        Self {
+            witness_lattice: SISScalar::lattice_for(width, rng),
+            error_lattice: SISScalar::lattice_for(height, rng),
+        }
+    }
+
+    /// Commit to a satisfying witness of a plain R1CS, producing an instance with `u = 1` and
+    /// `E = 0`.
+    pub fn commit_r1cs(
+        &self,
+        r1cs: &R1CS<E>,
+        witness: &Vector<E>,
+    ) -> (FoldedInstance<E>, FoldedWitness<E>) {
+        let witness = FoldedWitness {
+            witness: witness.clone(),
+            error: Vector::new(r1cs.dimension().0),
+        };
+        (self.commit(E::one(), &witness), witness)
+    }
+
+    /// Commit to a relaxed witness with scalar `u`.
+    pub fn commit(&self, u: E, witness: &FoldedWitness<E>) -> FoldedInstance<E> {
+        FoldedInstance {
+            u,
+            witness_commitment: SISScalar::commit(
+                witness.witness.clone(),
+                self.witness_lattice.clone(),
+            ),
+            error_commitment: SISScalar::commit(witness.error.clone(), self.error_lattice.clone()),
+        }
+    }
+
+    /// Fold two committed instances of `r1cs` into one. With high probability the output instance is
+    /// satisfied only if both inputs are satisfied.
+    pub fn fold_prove<H: ElementHasher<E>>(
+        &self,
+        r1cs: &R1CS<E>,
+        (instance_1, witness_1): (&FoldedInstance<E>, &FoldedWitness<E>),
+        (instance_2, witness_2): (&FoldedInstance<E>, &FoldedWitness<E>),
+        hasher: &mut H,
+    ) -> (FoldingProof<E>, FoldedInstance<E>, FoldedWitness<E>) {
+        let cross_term = RelaxedR1CS::cross_term(
+            r1cs,
+            (instance_1.u, &witness_1.witness),
+            (instance_2.u, &witness_2.witness),
+        );
+        let proof = FoldingProof {
+            cross_term_commitment: SISScalar::commit(
+                cross_term.clone(),
+                self.error_lattice.clone(),
+            ),
+        };
+        let r = Self::challenge(r1cs, instance_1, instance_2, &proof, hasher);
+
+        let witness = FoldedWitness {
+            witness: witness_1.witness.clone() + &(witness_2.witness.clone() * r),
+            error: witness_1.error.clone()
+                + &(cross_term * r)
+                + &(witness_2.error.clone() * (r * r)),
+        };
+        let instance = Self::fold_instances(instance_1, instance_2, &proof, r);
+        (proof, instance, witness)
+    }
+
+    /// Fold two committed instances of `r1cs` using only public data.
+    pub fn fold_verify<H: ElementHasher<E>>(
+        &self,
+        r1cs: &R1CS<E>,
+        instance_1: &FoldedInstance<E>,
+        instance_2: &FoldedInstance<E>,
+        proof: &FoldingProof<E>,
+        hasher: &mut H,
+    ) -> FoldedInstance<E> {
+        let r = Self::challenge(r1cs, instance_1, instance_2, proof, hasher);
+        Self::fold_instances(instance_1, instance_2, proof, r)
+    }
+
+    /// Check that a witness opens a committed instance and satisfies the relaxed R1CS.
+    pub fn verify_witness(
+        &self,
+        r1cs: &R1CS<E>,
+        instance: &FoldedInstance<E>,
+        witness: &FoldedWitness<E>,
+    ) -> Result<()> {
+        let expected = self.commit(instance.u, witness);
+        if expected.witness_commitment.commitment != instance.witness_commitment.commitment {
+            anyhow::bail!("Folded instance witness commitment mismatch");
+        }
+        if expected.error_commitment.commitment != instance.error_commitment.commitment {
+            anyhow::bail!("Folded instance error commitment mismatch");
+        }
+        let relaxed = RelaxedR1CS {
+            r1cs: r1cs.clone(),
+            u: instance.u,
+            error: witness.error.clone(),
+        };
+        if !relaxed.eval(&witness.witness)?.is_zero() {
+            anyhow::bail!("Folded witness does not satisfy the relaxed R1CS");
+        }
+        Ok(())
+    }
+
+    /// Fiat-Shamir challenge bound to the R1CS being folded, both instances and the cross term.
+    fn challenge<H: ElementHasher<E>>(
+        r1cs: &R1CS<E>,
+        instance_1: &FoldedInstance<E>,
+        instance_2: &FoldedInstance<E>,
+        proof: &FoldingProof<E>,
+        hasher: &mut H,
+    ) -> E {
+        r1cs.write_transcript(hasher);
+        for instance in [instance_1, instance_2] {
+            hasher.write_element(&instance.u);
+            for v in instance
+                .witness_commitment
+                .commitment
+                .iter()
+                .chain(instance.error_commitment.commitment.iter())
+            {
+                hasher.write_element(v);
+            }
+        }
+        for v in proof.cross_term_commitment.commitment.iter() {
+            hasher.write_element(v);
+        }
+        hasher.challenge()
+    }
+
+    fn fold_instances(
+        instance_1: &FoldedInstance<E>,
+        instance_2: &FoldedInstance<E>,
+        proof: &FoldingProof<E>,
+        r: E,
+    ) -> FoldedInstance<E> {
+        FoldedInstance {
+            u: instance_1.u + r * instance_2.u,
+            witness_commitment: instance_1.witness_commitment.clone()
+                + &(instance_2.witness_commitment.clone() * r),
+            error_commitment: instance_1.error_commitment.clone()
+                + &(proof.cross_term_commitment.clone() * r)
+                + &(instance_2.error_commitment.clone() * (r * r)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TestHasher;
+    use crate::test::multiplication_r1cs;
+
+    type Field = OxfoiScalar;
+
+    /// Build a satisfying witness for `r1cs` as produced by `multiplication_r1cs`.
+    fn satisfying_witness<R: Rng>(r1cs: &R1CS<Field>, rng: &mut R) -> Vector<Field> {
+        let (height, width) = r1cs.dimension();
+        let mut witness = Vector::random(width, rng);
+        for i in 0..height {
+            let left = r1cs.a[i].iter().position(|v| !v.is_zero()).unwrap();
+            let right = r1cs.b[i].iter().position(|v| !v.is_zero()).unwrap();
+            witness[width / 2 + i] = witness[left] * witness[right];
+        }
+        witness
+    }
+
+    #[test]
+    fn fold_two() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness_1) = multiplication_r1cs::<Field, _>(5, 12, rng);
+        let witness_2 = satisfying_witness(&r1cs, rng);
+        assert!(r1cs.eval(&witness_2)?.is_zero());
+        let params = FoldingParams::setup(&r1cs, rng);
+        let (instance_1, witness_1) = params.commit_r1cs(&r1cs, &witness_1);
+        let (instance_2, witness_2) = params.commit_r1cs(&r1cs, &witness_2);
+        params.verify_witness(&r1cs, &instance_1, &witness_1)?;
+        params.verify_witness(&r1cs, &instance_2, &witness_2)?;
+
+        let (proof, folded, folded_witness) = params.fold_prove(
+            &r1cs,
+            (&instance_1, &witness_1),
+            (&instance_2, &witness_2),
+            &mut TestHasher::default(),
+        );
+        params.verify_witness(&r1cs, &folded, &folded_witness)?;
+
+        let verified = params.fold_verify(
+            &r1cs,
+            &instance_1,
+            &instance_2,
+            &proof,
+            &mut TestHasher::default(),
+        );
+        assert_eq!(verified.u, folded.u);
+        params.verify_witness(&r1cs, &verified, &folded_witness)?;
+        Ok(())
+    }
+
+    #[test]
+    fn fold_chain() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness) = multiplication_r1cs::<Field, _>(6, 16, rng);
+        let params = FoldingParams::setup(&r1cs, rng);
+        let (mut running, mut running_witness) = params.commit_r1cs(&r1cs, &witness);
+        let mut hasher = TestHasher::default();
+        for _ in 0..5 {
+            let (instance, witness) = params.commit_r1cs(&r1cs, &satisfying_witness(&r1cs, rng));
+            let (proof, folded, folded_witness) = params.fold_prove(
+                &r1cs,
+                (&running, &running_witness),
+                (&instance, &witness),
+                &mut hasher.clone(),
+            );
+            running = params.fold_verify(&r1cs, &running, &instance, &proof, &mut hasher);
+            assert_eq!(running.u, folded.u);
+            running_witness = folded_witness;
+            params.verify_witness(&r1cs, &running, &running_witness)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fold_rejects_invalid() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness_1) = multiplication_r1cs::<Field, _>(5, 12, rng);
+        let params = FoldingParams::setup(&r1cs, rng);
+        let mut witness_2 = satisfying_witness(&r1cs, rng);
+        witness_2[6] += Field::one();
+        assert!(!r1cs.eval(&witness_2)?.is_zero());
+        let (instance_1, witness_1) = params.commit_r1cs(&r1cs, &witness_1);
+        let (instance_2, witness_2) = params.commit_r1cs(&r1cs, &witness_2);
+        assert!(
+            params
+                .verify_witness(&r1cs, &instance_2, &witness_2)
+                .is_err()
+        );
+
+        // folding an unsatisfied instance yields an unsatisfied instance
+        let (_, folded, folded_witness) = params.fold_prove(
+            &r1cs,
+            (&instance_1, &witness_1),
+            (&instance_2, &witness_2),
+            &mut TestHasher::default(),
+        );
+        assert!(
+            params
+                .verify_witness(&r1cs, &folded, &folded_witness)
+                .is_err()
+        );
+
+        // a tampered cross term commitment changes the folded error commitment
+        let witness_2 = FoldedWitness {
+            witness: satisfying_witness(&r1cs, rng),
+            error: Vector::new(5),
+        };
+        let instance_2 = params.commit(Field::one(), &witness_2);
+        let (mut proof, _, folded_witness) = params.fold_prove(
+            &r1cs,
+            (&instance_1, &witness_1),
+            (&instance_2, &witness_2),
+            &mut TestHasher::default(),
+        );
+        proof.cross_term_commitment.commitment[0] += Field::one();
+        let tampered = params.fold_verify(
+            &r1cs,
+            &instance_1,
+            &instance_2,
+            &proof,
+            &mut TestHasher::default(),
+        );
+        assert!(
+            params
+                .verify_witness(&r1cs, &tampered, &folded_witness)
+                .is_err()
+        );
+
+        // the challenge is bound to the R1CS, not just its shape
+        proof.cross_term_commitment.commitment[0] -= Field::one();
+        let (other, _) = multiplication_r1cs::<Field, _>(5, 12, rng);
+        let honest = params.fold_verify(
+            &r1cs,
+            &instance_1,
+            &instance_2,
+            &proof,
+            &mut TestHasher::default(),
+        );
+        let rebound = params.fold_verify(
+            &other,
+            &instance_1,
+            &instance_2,
+            &proof,
+            &mut TestHasher::default(),
+        );
+        assert_ne!(honest.u, rebound.u);
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use crate::*;
+
+impl<E: Element> ConstraintSystem<E> {
+    /// Decompose `x` into `num_bits` little endian boolean variables, matching
+    /// `Element::as_le_bits_vec(1)`.
+    ///
+    /// If `2^num_bits` is less than the cardinality this also checks `x < 2^num_bits`. Otherwise
+    /// the bits are constrained to the canonical representation of `x`, below the cardinality.
+    ///
+    /// Panics if `x` does not fit in `num_bits` bits.
+    pub fn unpack_bits(&mut self, x: &LinearCombination<E>, num_bits: usize) -> Vec<usize> {
+        let value: u128 = self.value(x).into();
+        assert!(
+            num_bits >= u128::BITS as usize || value >> num_bits == 0,
+            "ConstraintSystem::unpack_bits value does not fit in {} bits",
+            num_bits
+        );
+        let bits = (0..num_bits)
+            .map(|i| self.alloc_boolean(i < u128::BITS as usize && (value >> i) & 1 == 1))
+            .collect::<Vec<_>>();
+        let packed = self.pack(&bits);
+        self.enforce_equal(&packed, x);
+        if num_bits >= u128::BITS as usize || 1u128 << num_bits > E::CARDINALITY {
+            self.enforce_bits_less_than(&bits, E::CARDINALITY);
+        }
+        bits
+    }
+
+    /// Decompose `x` into limbs of `limb_bits` bits, matching `Element::as_le_bits_vec`.
+    pub fn unpack_limbs(&mut self, x: &LinearCombination<E>, limb_bits: usize) -> Vec<usize> {
+        let num_limbs = E::bits_vec_len(limb_bits);
+        let bits = self.unpack_bits(x, num_limbs * limb_bits);
+        bits.chunks(limb_bits)
+            .map(|chunk| {
+                let packed = self.pack(chunk);
+                let limb = self.alloc(self.value(&packed));
+                self.enforce_equal(&LinearCombination::variable(limb), &packed);
+                limb
+            })
+            .collect()
+    }
+
+    /// The little endian packing `sum_i 2^i * bits[i]`. Adds no constraints, the bits must be
+    /// constrained to be boolean.
+    pub fn pack(&self, bits: &[usize]) -> LinearCombination<E> {
+        let mut out = LinearCombination::zero();
+        let mut coefficient = E::one();
+        for bit in bits {
+            out = out.add_term(*bit, coefficient);
+            coefficient = coefficient + coefficient;
+        }
+        out
+    }
+
+    /// Enforce `0 <= x < 2^num_bits`.
+    ///
+    /// Panics if `2^num_bits` is not less than the cardinality, which would not restrict `x`.
+    pub fn range_check(&mut self, x: &LinearCombination<E>, num_bits: usize) {
+        assert!(
+            num_bits < u128::BITS as usize && 1u128 << num_bits < E::CARDINALITY,
+            "ConstraintSystem::range_check 2^{} is not less than the field cardinality",
+            num_bits
+        );
+        self.unpack_bits(x, num_bits);
+    }
+
+    /// Enforce `0 <= x < 2^num_bits` like `range_check`, but a witness outside the range
+    /// allocates the low bits of `x` and leaves the system unsatisfied instead of panicking, so
+    /// gadgets can be built for invalid witnesses.
+    ///
+    /// Panics if `2^num_bits` is not less than the cardinality, which would not restrict `x`.
+    pub fn range_check_lenient(&mut self, x: &LinearCombination<E>, num_bits: usize) {
+        assert!(
+            num_bits < u128::BITS as usize && 1u128 << num_bits < E::CARDINALITY,
+            "ConstraintSystem::range_check_lenient 2^{} is not less than the field cardinality",
+            num_bits
+        );
+        let value: u128 = self.value(x).into();
+        let bits = (0..num_bits)
+            .map(|i| self.alloc_boolean((value >> i) & 1 == 1))
+            .collect::<Vec<_>>();
+        let packed = self.pack(&bits);
+        self.enforce_equal(&packed, x);
+    }
+
+    /// Enforce that the integer with little endian boolean variables `bits` is less than
+    /// `bound`, comparing from the most significant bit.
+    pub fn enforce_bits_less_than(&mut self, bits: &[usize], bound: u128) {
+        // `equal` is 1 while the bits seen so far match the bound, `less` accumulates the
+        // mutually exclusive events of the first differing bit being 0 where the bound has 1
+        let mut equal = self.one();
+        let mut less = LinearCombination::zero();
+        for (i, bit) in bits.iter().enumerate().rev() {
+            let bound_bit = i < u128::BITS as usize && (bound >> i) & 1 == 1;
+            let both =
+                LinearCombination::variable(self.mul(&equal, &LinearCombination::variable(*bit)));
+            if bound_bit {
+                less = less + &equal - &both;
+                equal = both;
+            } else {
+                equal = equal - &both;
+            }
+        }
+        self.enforce_equal(&less, &self.one());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn unpack_bits() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let value = Field::sample_rand(rng);
+        let mut cs = ConstraintSystem::<Field>::new();
+        let x = LinearCombination::variable(cs.alloc(value));
+        let bits = cs.unpack_bits(&x, Field::BIT_WIDTH);
+        let r1cs = cs.to_r1cs();
+        let witness = cs.witness();
+        assert!(r1cs.eval(&witness)?.is_zero());
+        let expected = value.as_le_bits_vec(1);
+        for (bit, expected) in bits.iter().zip(expected.iter()) {
+            assert_eq!(witness[*bit], *expected);
+        }
+
+        // flipping a bit breaks the packing
+        let mut bad = witness.clone();
+        bad[bits[3]] = Field::one() - bad[bits[3]];
+        assert!(!r1cs.eval(&bad)?.is_zero());
+
+        // the non canonical decomposition of x + p is rejected
+        let mut cs = ConstraintSystem::<Field>::new();
+        let x = LinearCombination::variable(cs.alloc(Field::from(5)));
+        let bits = cs.unpack_bits(&x, Field::BIT_WIDTH);
+        let r1cs = cs.to_r1cs();
+        let mut witness = cs.witness();
+        assert!(r1cs.eval(&witness)?.is_zero());
+        let non_canonical = 5 + Field::CARDINALITY;
+        for (i, bit) in bits.iter().enumerate() {
+            witness[*bit] = Field::from((non_canonical >> i) & 1);
+        }
+        assert_eq!(cs.pack(&bits).evaluate(witness.as_slice()), Field::from(5));
+        assert!(!r1cs.eval(&witness)?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn to_bits_small_field() -> Result<()> {
+        for value in 0..7u128 {
+            let mut cs = ConstraintSystem::<SevenScalar>::new();
+            let x = LinearCombination::variable(cs.alloc(SevenScalar::from(value)));
+            let bits = cs.unpack_bits(&x, SevenScalar::BIT_WIDTH);
+            let r1cs = cs.to_r1cs();
+            let mut witness = cs.witness();
+            assert!(r1cs.eval(&witness)?.is_zero());
+            if value < 3 {
+                // x + 7 has a second decomposition in 8 bits
+                for (i, bit) in bits.iter().enumerate() {
+                    witness[*bit] = SevenScalar::from(((value + 7) >> i) & 1);
+                }
+                assert!(!r1cs.eval(&witness)?.is_zero());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unpack_limbs() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let value = Field::sample_rand(rng);
+        for limb_bits in [1, 8, 13, 32] {
+            let mut cs = ConstraintSystem::<Field>::new();
+            let x = LinearCombination::variable(cs.alloc(value));
+            let limbs = cs.unpack_limbs(&x, limb_bits);
+            let r1cs = cs.to_r1cs();
+            let mut witness = cs.witness();
+            assert!(r1cs.eval(&witness)?.is_zero());
+            let expected = value.as_le_bits_vec(limb_bits);
+            assert_eq!(limbs.len(), expected.len());
+            for (limb, expected) in limbs.iter().zip(expected.iter()) {
+                assert_eq!(witness[*limb], *expected);
+            }
+            witness[limbs[0]] += Field::one();
+            assert!(!r1cs.eval(&witness)?.is_zero());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn range_check() -> Result<()> {
+        let mut cs = ConstraintSystem::<Field>::new();
+        let x = LinearCombination::variable(cs.alloc(Field::from(255)));
+        cs.range_check(&x, 8);
+        let r1cs = cs.to_r1cs();
+        let witness = cs.witness();
+        assert!(r1cs.eval(&witness)?.is_zero());
+
+        // 256 is out of range for any assignment of the bits
+        let mut bad = witness.clone();
+        bad[1] = Field::from(256);
+        assert!(!r1cs.eval(&bad)?.is_zero());
+        let mut bad = witness.clone();
+        bad[1] = Field::negone();
+        assert!(!r1cs.eval(&bad)?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn range_check_lenient() -> Result<()> {
+        let mut cs = ConstraintSystem::<Field>::new();
+        let x = LinearCombination::variable(cs.alloc(Field::from(255)));
+        cs.range_check_lenient(&x, 8);
+        assert!(cs.to_r1cs().eval(&cs.witness())?.is_zero());
+
+        // out of range values build an unsatisfied system
+        for value in [Field::from(256), Field::negone()] {
+            let mut cs = ConstraintSystem::<Field>::new();
+            let x = LinearCombination::variable(cs.alloc(value));
+            cs.range_check_lenient(&x, 8);
+            assert!(!cs.to_r1cs().eval(&cs.witness())?.is_zero());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn pack_unpack() -> Result<()> {
+        let mut cs = ConstraintSystem::<Field>::new();
+        let bits = [true, false, true, true]
+            .iter()
+            .map(|b| cs.alloc_boolean(*b))
+            .collect::<Vec<_>>();
+        let packed = cs.pack(&bits);
+        assert_eq!(cs.value(&packed), Field::from(13));
+        let unpacked = cs.unpack_bits(&packed, 4);
+        for (a, b) in bits.iter().zip(&unpacked) {
+            cs.enforce_equal(
+                &LinearCombination::variable(*a),
+                &LinearCombination::variable(*b),
+            );
+        }
+        let r1cs = cs.to_r1cs();
+        let mut witness = cs.witness();
+        assert!(r1cs.eval(&witness)?.is_zero());
+        witness[unpacked[0]] = Field::zero();
+        assert!(!r1cs.eval(&witness)?.is_zero());
+        Ok(())
+    }
+}
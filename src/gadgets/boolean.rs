@@ -0,0 +1,158 @@
+use crate::*;
+
+impl<E: Element> ConstraintSystem<E> {
+    /// Allocate a variable constrained to be 0 or 1.
+    pub fn alloc_boolean(&mut self, value: bool) -> usize {
+        let v = self.alloc(E::from(value as u128));
+        self.enforce_boolean(&LinearCombination::variable(v));
+        v
+    }
+
+    /// Enforce `x * (x - 1) = 0`.
+    pub fn enforce_boolean(&mut self, x: &LinearCombination<E>) {
+        self.enforce(
+            x.clone(),
+            x.clone() - &self.one(),
+            LinearCombination::zero(),
+        );
+    }
+
+    /// Enforce `a = b`.
+    pub fn enforce_equal(&mut self, a: &LinearCombination<E>, b: &LinearCombination<E>) {
+        self.enforce(a.clone() - b, self.one(), LinearCombination::zero());
+    }
+
+    /// Allocate `a * b`.
+    pub fn mul(&mut self, a: &LinearCombination<E>, b: &LinearCombination<E>) -> usize {
+        let out = self.alloc(self.value(a) * self.value(b));
+        self.enforce(a.clone(), b.clone(), LinearCombination::variable(out));
+        out
+    }
+
+    /// Allocate a boolean that is 1 if `x` is zero and 0 otherwise.
+    ///
+    /// Uses the inverse of `x` as a hint: `x * inv = 1 - out` and `x * out = 0`.
+    pub fn is_zero(&mut self, x: &LinearCombination<E>) -> usize {
+        let value = self.value(x);
+        let out = self.alloc(E::from(value.is_zero() as u128));
+        let inv = self.alloc(value.inverse().unwrap_or(E::zero()));
+        let out_lc = LinearCombination::variable(out);
+        self.enforce(
+            x.clone(),
+            LinearCombination::variable(inv),
+            self.one() - &out_lc,
+        );
+        self.enforce(x.clone(), out_lc, LinearCombination::zero());
+        out
+    }
+
+    /// Allocate a boolean that is 1 if `a = b` and 0 otherwise.
+    pub fn is_equal(&mut self, a: &LinearCombination<E>, b: &LinearCombination<E>) -> usize {
+        self.is_zero(&(a.clone() - b))
+    }
+
+    /// Allocate `a` if `condition` is 1 and `b` if it is 0, `out = b + condition * (a - b)`.
+    /// `condition` must be constrained to be boolean.
+    pub fn select(
+        &mut self,
+        condition: &LinearCombination<E>,
+        a: &LinearCombination<E>,
+        b: &LinearCombination<E>,
+    ) -> usize {
+        let diff = a.clone() - b;
+        let out = self.alloc(self.value(b) + self.value(condition) * self.value(&diff));
+        self.enforce(
+            condition.clone(),
+            diff,
+            LinearCombination::variable(out) - b,
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn boolean() -> Result<()> {
+        for value in [false, true] {
+            let mut cs = ConstraintSystem::<Field>::new();
+            let v = cs.alloc_boolean(value);
+            let r1cs = cs.to_r1cs();
+            let mut witness = cs.witness();
+            assert!(r1cs.eval(&witness)?.is_zero());
+            witness[v] = Field::from(2);
+            assert!(!r1cs.eval(&witness)?.is_zero());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn is_zero_and_equal() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        for value in [Field::zero(), Field::one(), Field::sample_rand(rng)] {
+            let mut cs = ConstraintSystem::<Field>::new();
+            let x = LinearCombination::variable(cs.alloc(value));
+            let out = cs.is_zero(&x);
+            let r1cs = cs.to_r1cs();
+            let witness = cs.witness();
+            assert!(r1cs.eval(&witness)?.is_zero());
+            assert_eq!(witness[out], Field::from(value.is_zero() as u128));
+
+            // claiming the opposite result fails for every choice of the inverse hint
+            let mut bad = witness.clone();
+            bad[out] = Field::one() - witness[out];
+            for inv in [
+                Field::zero(),
+                Field::one(),
+                value.inverse().unwrap_or_default(),
+            ] {
+                bad[out + 1] = inv;
+                assert!(!r1cs.eval(&bad)?.is_zero());
+            }
+        }
+
+        let mut cs = ConstraintSystem::<Field>::new();
+        let a = LinearCombination::variable(cs.alloc(Field::from(9)));
+        let b = LinearCombination::variable(cs.alloc(Field::from(9)));
+        let c = LinearCombination::variable(cs.alloc(Field::from(10)));
+        let ab = cs.is_equal(&a, &b);
+        let ac = cs.is_equal(&a, &c);
+        cs.enforce_equal(&a, &b);
+        let r1cs = cs.to_r1cs();
+        let mut witness = cs.witness();
+        assert!(r1cs.eval(&witness)?.is_zero());
+        assert_eq!(witness[ab], Field::one());
+        assert_eq!(witness[ac], Field::zero());
+        witness[2] = Field::from(10);
+        assert!(!r1cs.eval(&witness)?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn select() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let a_val = Field::sample_rand(rng);
+        let b_val = Field::sample_rand(rng);
+        for condition in [false, true] {
+            let mut cs = ConstraintSystem::<Field>::new();
+            let c = LinearCombination::variable(cs.alloc_boolean(condition));
+            let a = LinearCombination::variable(cs.alloc(a_val));
+            let b = LinearCombination::variable(cs.alloc(b_val));
+            let out = cs.select(&c, &a, &b);
+            let r1cs = cs.to_r1cs();
+            let mut witness = cs.witness();
+            assert!(r1cs.eval(&witness)?.is_zero());
+            assert_eq!(witness[out], if condition { a_val } else { b_val });
+
+            witness[out] = if condition { b_val } else { a_val };
+            assert!(!r1cs.eval(&witness)?.is_zero());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use crate::*;
+
+impl<E: Element> ConstraintSystem<E> {
+    /// Allocate a boolean that is 1 if `a < b` and 0 otherwise, for `a` and `b` in
+    /// `[0, 2^num_bits)`. The inputs are range checked.
+    ///
+    /// Decomposes `a - b + 2^num_bits` into `num_bits + 1` bits, the top bit is set exactly when
+    /// `a >= b`.
+    ///
+    /// Panics if `2^(num_bits + 1)` is not less than the cardinality.
+    pub fn less_than(
+        &mut self,
+        a: &LinearCombination<E>,
+        b: &LinearCombination<E>,
+        num_bits: usize,
+    ) -> usize {
+        assert!(
+            num_bits + 1 < u128::BITS as usize && 1u128 << (num_bits + 1) < E::CARDINALITY,
+            "ConstraintSystem::less_than 2^{} is not less than the field cardinality",
+            num_bits + 1
+        );
+        self.range_check(a, num_bits);
+        self.range_check(b, num_bits);
+        let offset = a.clone() - b + &LinearCombination::constant(E::from(1u128 << num_bits));
+        let bits = self.unpack_bits(&offset, num_bits + 1);
+        let top = LinearCombination::variable(bits[num_bits]);
+        let out = self.alloc(self.value(&(self.one() - &top)));
+        self.enforce_equal(&LinearCombination::variable(out), &(self.one() - &top));
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn less_than() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let num_bits = 16;
+        let mut cases = vec![(0, 0), (0, 1), (1, 0), (65535, 65535), (65534, 65535)];
+        for _ in 0..10 {
+            cases.push((rng.random_range(0..65536), rng.random_range(0..65536)));
+        }
+        for (a_val, b_val) in cases {
+            let mut cs = ConstraintSystem::<Field>::new();
+            let a = LinearCombination::variable(cs.alloc(Field::from(a_val)));
+            let b = LinearCombination::variable(cs.alloc(Field::from(b_val)));
+            let out = cs.less_than(&a, &b, num_bits);
+            let r1cs = cs.to_r1cs();
+            let mut witness = cs.witness();
+            assert!(r1cs.eval(&witness)?.is_zero());
+            assert_eq!(witness[out], Field::from((a_val < b_val) as u128));
+
+            witness[out] = Field::one() - witness[out];
+            assert!(!r1cs.eval(&witness)?.is_zero());
+        }
+
+        // an input outside the range is rejected
+        let mut cs = ConstraintSystem::<Field>::new();
+        let a = LinearCombination::variable(cs.alloc(Field::from(3)));
+        let b = LinearCombination::variable(cs.alloc(Field::from(4)));
+        cs.less_than(&a, &b, num_bits);
+        let r1cs = cs.to_r1cs();
+        let mut witness = cs.witness();
+        witness[1] = Field::from(3 + (1 << num_bits));
+        assert!(!r1cs.eval(&witness)?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn less_than_largest() -> Result<()> {
+        // 2^63 < p < 2^64, so 62 bits is the widest comparison over the Oxfoi field
+        let mut cs = ConstraintSystem::<Field>::new();
+        let a = LinearCombination::variable(cs.alloc(Field::from((1u128 << 62) - 1)));
+        let b = LinearCombination::variable(cs.alloc(Field::zero()));
+        let out = cs.less_than(&a, &b, 62);
+        cs.assert_satisfied()?;
+        assert_eq!(cs.witness()[out], Field::zero());
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn less_than_too_wide() {
+        // a - b + 2^63 wraps modulo p, so the top bit would not decide the comparison
+        let mut cs = ConstraintSystem::<Field>::new();
+        let a = LinearCombination::variable(cs.alloc(Field::from((1u128 << 63) - 1)));
+        let b = LinearCombination::variable(cs.alloc(Field::zero()));
+        cs.less_than(&a, &b, 63);
+    }
+}
@@ -0,0 +1,5 @@
+mod bits;
+mod boolean;
+mod comparison;
+mod poseidon2;
+mod sis;
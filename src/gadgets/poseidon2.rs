@@ -0,0 +1,153 @@
+use crate::*;
+
+impl ConstraintSystem<OxfoiScalar> {
+    /// Apply `Poseidon2` to a state of linear combinations. Each S-box costs 4
+    /// constraints, the linear layers are free.
+    pub fn poseidon2_permutation(
+        &mut self,
+        state: &[LinearCombination<OxfoiScalar>; POSEIDON2_WIDTH],
+    ) -> [LinearCombination<OxfoiScalar>; POSEIDON2_WIDTH] {
+        let permutation = Poseidon2::get();
+        let external = Poseidon2::external_matrix();
+        let internal = permutation.internal_matrix();
+        let (first, last) = permutation
+            .external_constants
+            .split_at(permutation.external_constants.len() / 2);
+
+        let mut state = Self::apply_matrix(&external, state);
+        for constants in first {
+            state = self.poseidon2_full_round(&external, &state, constants);
+        }
+        for constant in &permutation.internal_constants {
+            let x = state[0].clone() + &LinearCombination::constant(*constant);
+            state[0] = self.poseidon2_sbox(&x);
+            state = Self::apply_matrix(&internal, &state);
+        }
+        for constants in last {
+            state = self.poseidon2_full_round(&external, &state, constants);
+        }
+        state
+    }
+
+    /// Hash a sequence of linear combinations, matching `Poseidon2::hash`.
+    pub fn poseidon2_hash(
+        &mut self,
+        input: &[LinearCombination<OxfoiScalar>],
+    ) -> LinearCombination<OxfoiScalar> {
+        let mut state: [LinearCombination<OxfoiScalar>; POSEIDON2_WIDTH] = Default::default();
+        let mut position = 0;
+        for v in input {
+            state[position] = state[position].clone() + v;
+            position += 1;
+            if position == POSEIDON2_RATE {
+                state = self.poseidon2_permutation(&state);
+                position = 0;
+            }
+        }
+        state[position] = state[position].clone() + &self.one();
+        let [out, ..] = self.poseidon2_permutation(&state);
+        out
+    }
+
+    fn poseidon2_full_round(
+        &mut self,
+        external: &Matrix<OxfoiScalar>,
+        state: &[LinearCombination<OxfoiScalar>; POSEIDON2_WIDTH],
+        constants: &[OxfoiScalar; POSEIDON2_WIDTH],
+    ) -> [LinearCombination<OxfoiScalar>; POSEIDON2_WIDTH] {
+        let state: [LinearCombination<OxfoiScalar>; POSEIDON2_WIDTH] = std::array::from_fn(|i| {
+            let x = state[i].clone() + &LinearCombination::constant(constants[i]);
+            self.poseidon2_sbox(&x)
+        });
+        Self::apply_matrix(external, &state)
+    }
+
+    /// Allocate `x^7` with the chain `x^2, x^4, x^6, x^7`.
+    fn poseidon2_sbox(
+        &mut self,
+        x: &LinearCombination<OxfoiScalar>,
+    ) -> LinearCombination<OxfoiScalar> {
+        let x2 = LinearCombination::variable(self.mul(x, x));
+        let x4 = LinearCombination::variable(self.mul(&x2, &x2));
+        let x6 = LinearCombination::variable(self.mul(&x4, &x2));
+        LinearCombination::variable(self.mul(&x6, x))
+    }
+
+    fn apply_matrix(
+        matrix: &Matrix<OxfoiScalar>,
+        state: &[LinearCombination<OxfoiScalar>; POSEIDON2_WIDTH],
+    ) -> [LinearCombination<OxfoiScalar>; POSEIDON2_WIDTH] {
+        std::array::from_fn(|i| {
+            matrix[i]
+                .iter()
+                .zip(state)
+                .fold(LinearCombination::zero(), |acc, (m, x)| {
+                    acc + &(x.clone() * *m)
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn poseidon2_permutation_gadget() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let input: [Field; POSEIDON2_WIDTH] = std::array::from_fn(|_| Field::sample_rand(rng));
+        let mut expected = input;
+        Poseidon2::get().permute(&mut expected);
+
+        let mut cs = ConstraintSystem::<Field>::new();
+        let state = input.map(|v| LinearCombination::variable(cs.alloc(v)));
+        let output = cs.poseidon2_permutation(&state);
+        // 8 full rounds of 8 S-boxes and 22 partial rounds of 1 S-box, 4 constraints each
+        assert_eq!(cs.num_constraints(), 4 * (8 * 8 + 22));
+        let outputs = output.map(|lc| {
+            let v = cs.alloc(cs.value(&lc));
+            cs.enforce_equal(&LinearCombination::variable(v), &lc);
+            v
+        });
+        let r1cs = cs.to_r1cs();
+        let mut witness = cs.witness();
+        assert!(r1cs.eval(&witness)?.is_zero());
+        for (v, expected) in outputs.iter().zip(expected) {
+            assert_eq!(witness[*v], expected);
+        }
+
+        witness[outputs[3]] += Field::one();
+        assert!(!r1cs.eval(&witness)?.is_zero());
+        let mut witness = cs.witness();
+        witness[20] += Field::one();
+        assert!(!r1cs.eval(&witness)?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn poseidon2_hash_gadget() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        for len in [0, 1, 3, 4, 9] {
+            let input = Vector::<Field>::random(len, rng);
+            let mut cs = ConstraintSystem::<Field>::new();
+            let vars = input
+                .iter()
+                .map(|v| LinearCombination::variable(cs.alloc(*v)))
+                .collect::<Vec<_>>();
+            let out = cs.poseidon2_hash(&vars);
+            assert_eq!(cs.value(&out), Poseidon2::get().hash(input.as_slice()));
+
+            let mut hasher = Poseidon2Hasher::default();
+            for v in input.iter() {
+                hasher.write_element(v);
+            }
+            assert_eq!(cs.value(&out), hasher.finish());
+            cs.assert_satisfied()?;
+        }
+        Ok(())
+    }
+}
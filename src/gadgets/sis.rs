@@ -0,0 +1,129 @@
+use crate::*;
+
+impl<E: Element> ConstraintSystem<E> {
+    /// Enforce `|x| <= bound` for the centered representation of `x`, matching
+    /// `Vector::assert_norm_inf`.
+    ///
+    /// Range checks `x + bound` and `bound - x` to `k` bits with `2^k > 2 * bound`. Both are
+    /// nonnegative and at most `2 * bound` only if `x` is in `[-bound, bound]`.
+    ///
+    /// Panics if `2^(k + 1)` is not less than the cardinality, in which case the range checks
+    /// could wrap around the field.
+    pub fn enforce_centered_bound(&mut self, x: &LinearCombination<E>, bound: u128) {
+        let num_bits = (u128::BITS - (2 * bound).leading_zeros()) as usize;
+        assert!(
+            num_bits + 1 < u128::BITS as usize && 1u128 << (num_bits + 1) < E::CARDINALITY,
+            "ConstraintSystem::enforce_centered_bound bound {} is too large for the field",
+            bound
+        );
+        let bound = LinearCombination::constant(E::from(bound));
+        self.range_check_lenient(&(x.clone() + &bound), num_bits);
+        self.range_check_lenient(&(bound - x), num_bits);
+    }
+
+    /// Enforce that `message` opens an `SISScalar` commitment, `lattice * message = commitment`
+    /// with every entry of `message` within `max_dist` of zero, as checked by
+    /// `SISScalar::try_open`. The lattice and commitment are public constants of the system.
+    ///
+    /// Panics if the dimensions of the lattice, commitment and message do not agree.
+    pub fn enforce_sis_opening(
+        &mut self,
+        lattice: &Matrix<E>,
+        commitment: &Vector<E>,
+        message: &[LinearCombination<E>],
+        max_dist: u128,
+    ) {
+        assert_eq!(
+            lattice.dimension(),
+            (commitment.len(), message.len()),
+            "ConstraintSystem::enforce_sis_opening lattice dimension mismatch"
+        );
+        for m in message {
+            self.enforce_centered_bound(m, max_dist);
+        }
+        for (row, c) in lattice.iter().zip(commitment.iter()) {
+            let product = row
+                .iter()
+                .zip(message)
+                .fold(LinearCombination::zero(), |acc, (a, m)| {
+                    acc + &(m.clone() * *a)
+                });
+            self.enforce_equal(&product, &LinearCombination::constant(*c));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    fn build(
+        commitment: &SISScalar<Field>,
+        message: &Vector<Field>,
+        max_dist: u128,
+    ) -> (R1CS<Field>, Vector<Field>) {
+        let mut cs = ConstraintSystem::<Field>::new();
+        let message = message
+            .iter()
+            .map(|v| LinearCombination::variable(cs.alloc(*v)))
+            .collect::<Vec<_>>();
+        cs.enforce_sis_opening(
+            commitment.lattice(),
+            &commitment.commitment,
+            &message,
+            max_dist,
+        );
+        (cs.to_r1cs(), cs.witness())
+    }
+
+    #[test]
+    fn centered_bound() -> Result<()> {
+        for bound in [0, 1, 3, 4, 100] {
+            for value in -(bound as i32) - 2..=bound as i32 + 2 {
+                let mut cs = ConstraintSystem::<Field>::new();
+                let x = LinearCombination::variable(cs.alloc(Field::at_displacement(value)));
+                cs.enforce_centered_bound(&x, bound);
+                let r1cs = cs.to_r1cs();
+                assert_eq!(
+                    r1cs.eval(&cs.witness())?.is_zero(),
+                    value.unsigned_abs() as u128 <= bound,
+                    "value {} bound {}",
+                    value,
+                    bound
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sis_opening() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let max_dist = 2;
+        let len = 3;
+        let lattice = SISScalar::lattice_for(len, rng);
+        let message = Vector::from_centered(&[-2, 0, 1]);
+        let commitment = SISScalar::commit(message.clone(), lattice.clone());
+        commitment.try_open(&message, max_dist)?;
+        let (r1cs, witness) = build(&commitment, &message, max_dist);
+        assert!(r1cs.eval(&witness)?.is_zero());
+
+        // a different message does not open the commitment
+        let other = Vector::from_centered(&[-2, 1, 1]);
+        let (_, witness) = build(&commitment, &other, max_dist);
+        assert!(!r1cs.eval(&witness)?.is_zero());
+
+        // a message beyond the bound is rejected even though the commitment matches
+        let large = Vector::from_centered(&[-3, 0, 1]);
+        let commitment = SISScalar::commit(large.clone(), lattice);
+        assert!(commitment.try_open(&large, max_dist).is_err());
+        let (r1cs, witness) = build(&commitment, &large, max_dist);
+        assert!(!r1cs.eval(&witness)?.is_zero());
+        let (r1cs, witness) = build(&commitment, &large, max_dist + 1);
+        assert!(r1cs.eval(&witness)?.is_zero());
+        Ok(())
+    }
+}
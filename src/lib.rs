@@ -1,18 +1,41 @@
+mod bristol;
+mod ccs;
 mod commitments;
+mod constraint_system;
 mod fields;
+mod folding;
+mod gadgets;
 mod matrix;
+mod optimize;
+mod polynomial;
+mod poseidon2;
 mod probability;
+mod random_r1cs;
+mod relaxed_r1cs;
+mod spartan;
+mod sumcheck;
 mod vector;
 
 #[cfg(test)]
 mod test;
 
-use commitments::*;
+pub use bristol::*;
+pub use ccs::*;
+pub use commitments::*;
+pub use constraint_system::*;
 use fields::*;
-use matrix::*;
-use probability::*;
+pub use folding::*;
+pub use matrix::*;
+pub use optimize::*;
+pub use polynomial::*;
+pub use poseidon2::*;
+pub use probability::*;
 use rand::Rng;
-use vector::*;
+pub use random_r1cs::*;
+pub use relaxed_r1cs::*;
+pub use spartan::*;
+pub use sumcheck::*;
+pub use vector::*;
 
 use std::fmt::Display;
 use std::ops::Add;
@@ -29,6 +52,8 @@ use anyhow::Result;
 /// The default value should be the additive identity.
 pub trait Element:
     Sized
+    + Send
+    + Sync
     + Default
     + Copy
     + Display
@@ -66,6 +91,31 @@ pub trait Element:
         Self::from(0)
     }
 
+    /// Raise the element to the power `exp` by square and multiply.
+    fn pow(&self, mut exp: u128) -> Self {
+        let mut base = *self;
+        let mut out = Self::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                out *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        out
+    }
+
+    /// Multiplicative inverse of the element, or `None` for the additive identity.
+    ///
+    /// The default implementation uses Fermat's little theorem and assumes the cardinality is
+    /// prime.
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        Some(self.pow(Self::CARDINALITY - 2))
+    }
+
     /// Return the finite field element at a certain displacement.
     fn at_displacement(disp: i32) -> Self {
         if disp.abs() as u128 > Self::CARDINALITY / 2 {
@@ -98,7 +148,30 @@ pub trait Element:
         }
     }
 
-    fn sample_rand<R: Rng>(rng: &mut R) -> Self;
+    /// Sample a uniformly random element.
+    fn sample_rand<R: Rng>(rng: &mut R) -> Self {
+        Self::sample_below(Self::CARDINALITY, rng)
+    }
+
+    /// Sample an element uniformly from `[0, bound)` by rejection, without modulo bias.
+    fn sample_below<R: Rng>(bound: u128, rng: &mut R) -> Self {
+        assert!(
+            bound > 0 && bound <= Self::CARDINALITY,
+            "sample_below: bound {bound} outside of (0, {}]",
+            Self::CARDINALITY
+        );
+        Self::from(uniform_below(bound, rng))
+    }
+
+    /// Sample an element with displacement uniform in `[-bound, bound]`.
+    fn sample_centered<R: Rng>(bound: u128, rng: &mut R) -> Self {
+        // 2 * bound + 1 <= CARDINALITY, so distinct displacements are distinct elements
+        assert!(
+            bound <= (Self::CARDINALITY - 1) / 2,
+            "sample_centered: [-{bound}, {bound}] does not fit in the field"
+        );
+        Self::from(uniform_below(2 * bound + 1, rng)) - Self::from(bound)
+    }
 
     /// Determine either number of 2^bits elements in a single element, or upper bound of each
     /// chunked element given `bits` chunks.
@@ -159,6 +232,7 @@ pub trait Element:
     }
 }
 
+#[derive(Clone, Debug)]
 pub struct R1CS<E: Element> {
     a: Matrix<E>,
     b: Matrix<E>,
@@ -188,6 +262,21 @@ impl<E: Element> R1CS<E> {
         self.a.dimension()
     }
 
+    /// Write the dimension and every entry of A, B and C into a Fiat-Shamir transcript, binding
+    /// challenges to the statement.
+    pub fn write_transcript<H: ElementHasher<E>>(&self, hasher: &mut H) {
+        let (height, width) = self.dimension();
+        hasher.write(&(height as u64).to_le_bytes());
+        hasher.write(&(width as u64).to_le_bytes());
+        for matrix in [&self.a, &self.b, &self.c] {
+            for row in matrix.iter() {
+                for v in row {
+                    hasher.write_element(v);
+                }
+            }
+        }
+    }
+
     fn assert_consistency(&self) -> Result<()> {
         let dimension = self.a.dimension();
         if self.b.dimension() != dimension {
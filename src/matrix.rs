@@ -1,12 +1,57 @@
 use std::ops::IndexMut;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 use super::*;
 
+/// Number of rows of the right hand side kept hot in cache while computing a matrix product.
+const MUL_BLOCK_SIZE: usize = 64;
+/// Number of output rows computed by each task of a parallel matrix product.
+#[cfg(feature = "parallel")]
+const MUL_ROWS_PER_TASK: usize = 64;
+
+/// A dense matrix stored as a single contiguous buffer in row-major order.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Matrix<E: Element> {
     width: usize,
     height: usize,
-    entries: Vec<Vector<E>>,
+    entries: Vec<E>,
+}
+
+/// A view of a single column of a matrix. Entries are strided by the width of the matrix.
+#[derive(Debug, Clone, Copy)]
+pub struct Column<'a, E: Element> {
+    matrix: &'a Matrix<E>,
+    index: usize,
+}
+
+impl<'a, E: Element> Column<'a, E> {
+    pub fn len(&self) -> usize {
+        self.matrix.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a E> {
+        let matrix = self.matrix;
+        let index = self.index;
+        (0..matrix.height).map(move |row| &matrix.entries[row * matrix.width + index])
+    }
+
+    /// Copy the column into a vector.
+    pub fn to_vector(self) -> Vector<E> {
+        self.iter().copied().collect::<Vec<_>>().into()
+    }
+}
+
+impl<E: Element> Index<usize> for Column<'_, E> {
+    type Output = E;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.matrix[index][self.index]
+    }
 }
 
 impl<E: Element> Matrix<E> {
@@ -14,14 +59,36 @@ impl<E: Element> Matrix<E> {
         Self {
             width,
             height,
-            entries: vec![Vector::new(width); height],
+            entries: vec![E::default(); height * width],
         }
     }
 
     pub fn random<R: Rng>(height: usize, width: usize, rng: &mut R) -> Self {
-        let mut entries = Vec::with_capacity(height);
-        for _ in 0..height {
-            entries.push(Vector::random(width, rng));
+        let mut entries = Vec::with_capacity(height * width);
+        for _ in 0..(height * width) {
+            entries.push(E::sample_rand(rng));
+        }
+        Self {
+            width,
+            height,
+            entries,
+        }
+    }
+
+    /// Build a matrix from a list of rows.
+    ///
+    /// Panics if the rows are not of equal length.
+    pub fn from_rows(rows: Vec<Vector<E>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map(|row| row.len()).unwrap_or_default();
+        let mut entries = Vec::with_capacity(height * width);
+        for row in rows {
+            assert_eq!(
+                row.len(),
+                width,
+                "Matrix::from_rows cannot build a matrix from rows of unequal length"
+            );
+            entries.extend(row);
         }
         Self {
             width,
@@ -56,18 +123,24 @@ impl<E: Element> Matrix<E> {
     /// onto each row of `self`.
     ///
     /// Panics if matrices are not of equal height.
-    pub fn compose_horizontal(mut self, other: Self) -> Self {
+    pub fn compose_horizontal(self, other: Self) -> Self {
         let (self_height, self_width) = self.dimension();
         let (other_height, other_width) = other.dimension();
         assert_eq!(
             self_height, other_height,
             "Matrix::compose_horizontal cannot compose matrices of unequal height"
         );
-        self.width = self_width + other_width;
-        for (self_row, other_row) in self.entries.iter_mut().zip(other.entries.into_iter()) {
-            self_row.append(other_row);
+        let width = self_width + other_width;
+        let mut entries = Vec::with_capacity(self_height * width);
+        for (self_row, other_row) in self.iter().zip(other.iter()) {
+            entries.extend_from_slice(self_row);
+            entries.extend_from_slice(other_row);
+        }
+        Self {
+            width,
+            height: self_height,
+            entries,
         }
-        self
     }
 
     /// Take two matrices of equal width and create [self, other] by appending each column of `other`
@@ -87,21 +160,286 @@ impl<E: Element> Matrix<E> {
     }
 
     /// Get an iterator over each row of the matrix `self`.
-    pub fn iter(&self) -> impl Iterator<Item = &Vector<E>> {
-        self.entries.iter()
+    pub fn iter(&self) -> impl Iterator<Item = &[E]> {
+        (0..self.height).map(|i| self.row(i))
+    }
+
+    /// Get a slice of the entries in row `index`.
+    pub fn row(&self, index: usize) -> &[E] {
+        assert!(index < self.height, "requested row outside of matrix");
+        &self.entries[index * self.width..(index + 1) * self.width]
+    }
+
+    /// Get a mutable slice of the entries in row `index`.
+    pub fn row_mut(&mut self, index: usize) -> &mut [E] {
+        assert!(index < self.height, "requested row outside of matrix");
+        &mut self.entries[index * self.width..(index + 1) * self.width]
+    }
+
+    /// Get a strided view of the entries in column `index`.
+    pub fn column(&self, index: usize) -> Column<'_, E> {
+        assert!(index < self.width, "requested column outside of matrix");
+        Column {
+            matrix: self,
+            index,
+        }
+    }
+
+    /// Exchange rows `a` and `b` in place.
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        let (low, high) = (a.min(b), a.max(b));
+        let (head, tail) = self.entries.split_at_mut(high * self.width);
+        head[low * self.width..(low + 1) * self.width].swap_with_slice(&mut tail[..self.width]);
+    }
+
+    /// Subtract `factor` times row `src` from row `dst`.
+    fn sub_scaled_row(&mut self, dst: usize, src: usize, factor: E) {
+        assert_ne!(
+            dst, src,
+            "Matrix::sub_scaled_row cannot reduce a row by itself"
+        );
+        let width = self.width;
+        let (dst_row, src_row) = if dst < src {
+            let (head, tail) = self.entries.split_at_mut(src * width);
+            (&mut head[dst * width..(dst + 1) * width], &tail[..width])
+        } else {
+            let (head, tail) = self.entries.split_at_mut(dst * width);
+            (&mut tail[..width], &head[src * width..(src + 1) * width])
+        };
+        for (d, s) in dst_row.iter_mut().zip(src_row.iter()) {
+            *d -= *s * factor;
+        }
+    }
+
+    /// Create the (width, height) matrix with the rows of `self` as columns.
+    pub fn transpose(&self) -> Self {
+        let mut out = Self::zero(self.width, self.height);
+        for (i, row) in self.iter().enumerate() {
+            for (j, v) in row.iter().enumerate() {
+                out[j][i] = *v;
+            }
+        }
+        out
+    }
+
+    /// Kronecker (tensor) product of two matrices. For an (m, n) and a (p, q) matrix the result is
+    /// the (m * p, n * q) block matrix with block (i, j) equal to `self[i][j] * other`.
+    pub fn kron(&self, other: &Self) -> Self {
+        let height = self.height * other.height;
+        let width = self.width * other.width;
+        let mut entries = Vec::with_capacity(height * width);
+        for self_row in self.iter() {
+            for other_row in other.iter() {
+                for a in self_row {
+                    entries.extend(other_row.iter().map(|b| *a * *b));
+                }
+            }
+        }
+        Self {
+            width,
+            height,
+            entries,
+        }
+    }
+
+    /// Element-wise (Hadamard) product of two matrices of equal dimension.
+    ///
+    /// Panics if the matrices are not of equal dimension.
+    pub fn hadamard(mut self, rhs: &Self) -> Self {
+        assert_eq!(
+            self.dimension(),
+            rhs.dimension(),
+            "Matrix::hadamard cannot mul matrices of different dimension"
+        );
+        for (v, other) in self.entries.iter_mut().zip(rhs.entries.iter()) {
+            *v *= *other;
+        }
+        self
+    }
+
+    /// Reduce the matrix to reduced row echelon form using Gauss-Jordan elimination.
+    ///
+    /// Returns the reduced matrix and the column of the pivot in each non-zero row. Rows without
+    /// a pivot are zero and sorted to the bottom of the output.
+    pub fn row_echelon(&self) -> (Self, Vec<usize>) {
+        let mut out = self.clone();
+        let mut pivots = Vec::new();
+        for col in 0..self.width {
+            let pivot_row = pivots.len();
+            if pivot_row == self.height {
+                break;
+            }
+            let Some(found) = (pivot_row..self.height).find(|&i| !out[i][col].is_zero()) else {
+                continue;
+            };
+            out.swap_rows(pivot_row, found);
+            let inv = out[pivot_row][col]
+                .inverse()
+                .expect("Matrix::row_echelon non-zero pivot is not invertible");
+            for v in out.row_mut(pivot_row) {
+                *v *= inv;
+            }
+            for i in 0..self.height {
+                let factor = out[i][col];
+                if i == pivot_row || factor.is_zero() {
+                    continue;
+                }
+                out.sub_scaled_row(i, pivot_row, factor);
+            }
+            pivots.push(col);
+        }
+        (out, pivots)
+    }
+
+    /// Number of linearly independent rows (equivalently columns) of the matrix.
+    pub fn rank(&self) -> usize {
+        self.row_echelon().1.len()
+    }
+
+    /// Find a vector `x` such that `self * x = b`. If the system is underdetermined free
+    /// variables are set to zero. Returns `None` if the system is inconsistent.
+    ///
+    /// Panics if `b` is not of length equal to the height of the matrix.
+    pub fn solve(&self, b: &Vector<E>) -> Option<Vector<E>> {
+        assert_eq!(
+            self.height,
+            b.len(),
+            "Matrix::solve target vector length must equal matrix height"
+        );
+        let target = Self {
+            width: 1,
+            height: self.height,
+            entries: b.iter().copied().collect(),
+        };
+        let (reduced, pivots) = self.clone().compose_horizontal(target).row_echelon();
+        if pivots.last() == Some(&self.width) {
+            // a pivot in the augmented column means 0 = 1
+            return None;
+        }
+        let mut out = Vector::new(self.width);
+        for (row, col) in pivots.into_iter().enumerate() {
+            out[col] = reduced[row][self.width];
+        }
+        Some(out)
+    }
+
+    /// Compute a basis of the right kernel (nullspace) of the matrix. Each returned vector `v`
+    /// satisfies `self * v = 0`. The basis contains `width - rank` vectors.
+    pub fn kernel(&self) -> Vec<Vector<E>> {
+        let (reduced, pivots) = self.row_echelon();
+        let mut is_pivot = vec![false; self.width];
+        for col in &pivots {
+            is_pivot[*col] = true;
+        }
+        let mut out = Vec::with_capacity(self.width - pivots.len());
+        for free in (0..self.width).filter(|col| !is_pivot[*col]) {
+            let mut v = Vector::new(self.width);
+            v[free] = E::one();
+            for (row, col) in pivots.iter().enumerate() {
+                v[*col] = E::zero() - reduced[row][free];
+            }
+            out.push(v);
+        }
+        out
+    }
+
+    /// Compute the inverse of a square matrix. Returns `None` if the matrix is singular.
+    ///
+    /// Panics if the matrix is not square.
+    pub fn inverse(&self) -> Option<Self> {
+        assert_eq!(
+            self.height, self.width,
+            "Matrix::inverse cannot invert a non-square matrix"
+        );
+        let size = self.width;
+        let (reduced, pivots) = self
+            .clone()
+            .compose_horizontal(Self::identity(size))
+            .row_echelon();
+        // the left half reduces to the identity iff the matrix is full rank, so its pivots are
+        // the first `size` columns. Always true for an empty matrix.
+        if !pivots.iter().copied().take(size).eq(0..size) {
+            return None;
+        }
+        let mut out = Self::zero(size, size);
+        for i in 0..size {
+            out.row_mut(i).copy_from_slice(&reduced[i][size..]);
+        }
+        Some(out)
+    }
+
+    /// Compute the determinant of a square matrix by reduction to upper triangular form.
+    ///
+    /// Panics if the matrix is not square.
+    pub fn determinant(&self) -> E {
+        assert_eq!(
+            self.height, self.width,
+            "Matrix::determinant matrix is not square"
+        );
+        let mut m = self.clone();
+        let mut det = E::one();
+        for col in 0..self.width {
+            let Some(found) = (col..self.height).find(|&i| !m[i][col].is_zero()) else {
+                return E::zero();
+            };
+            if found != col {
+                m.swap_rows(col, found);
+                det = E::zero() - det;
+            }
+            let pivot = m[col][col];
+            det *= pivot;
+            let inv = pivot
+                .inverse()
+                .expect("Matrix::determinant non-zero pivot is not invertible");
+            for i in (col + 1)..self.height {
+                let factor = m[i][col] * inv;
+                if factor.is_zero() {
+                    continue;
+                }
+                m.sub_scaled_row(i, col, factor);
+            }
+        }
+        det
+    }
+
+    /// Compute rows `[first_row, first_row + out.len() / rhs.width)` of `self * rhs` into `out`.
+    ///
+    /// The inner dimension is processed in blocks of `MUL_BLOCK_SIZE` rows of `rhs` so that each
+    /// block stays in cache while it is accumulated into every output row.
+    fn mul_rows_into(&self, rhs: &Self, first_row: usize, out: &mut [E]) {
+        if rhs.width == 0 {
+            return;
+        }
+        for block_start in (0..self.width).step_by(MUL_BLOCK_SIZE) {
+            let block_end = (block_start + MUL_BLOCK_SIZE).min(self.width);
+            for (i, out_row) in out.chunks_exact_mut(rhs.width).enumerate() {
+                let self_row = &self.row(first_row + i)[block_start..block_end];
+                for (k, v) in self_row.iter().enumerate() {
+                    if v.is_zero() {
+                        continue;
+                    }
+                    for (out_v, rhs_v) in out_row.iter_mut().zip(rhs.row(block_start + k)) {
+                        *out_v += *v * *rhs_v;
+                    }
+                }
+            }
+        }
     }
 }
 
 impl<E: Element> Index<usize> for Matrix<E> {
-    type Output = Vector<E>;
+    type Output = [E];
     fn index(&self, index: usize) -> &Self::Output {
-        &self.entries[index]
+        self.row(index)
     }
 }
 
 impl<E: Element> IndexMut<usize> for Matrix<E> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.entries[index]
+        self.row_mut(index)
     }
 }
 
@@ -115,10 +453,8 @@ impl<E: Element> AddAssign<&Self> for Matrix<E> {
             self.height, rhs.height,
             "cannot add matrices of different height"
         );
-        for self_row in self.entries.iter_mut() {
-            for other_row in rhs.entries.iter() {
-                *self_row += other_row;
-            }
+        for (v, other) in self.entries.iter_mut().zip(rhs.entries.iter()) {
+            *v += *other;
         }
     }
 }
@@ -131,57 +467,141 @@ impl<E: Element> Add<&Self> for Matrix<E> {
     }
 }
 
-impl<E: Element> MulAssign<&Self> for Matrix<E> {
-    fn mul_assign(&mut self, rhs: &Self) {
+impl<E: Element> SubAssign<&Self> for Matrix<E> {
+    fn sub_assign(&mut self, rhs: &Self) {
         assert_eq!(
             self.width, rhs.width,
-            "cannot mul matrices of different width"
+            "cannot sub matrices of different width"
         );
         assert_eq!(
             self.height, rhs.height,
-            "cannot mul matrices of different height"
+            "cannot sub matrices of different height"
         );
-        for self_row in self.entries.iter_mut() {
-            for other_row in rhs.entries.iter() {
-                *self_row *= other_row;
-            }
+        for (v, other) in self.entries.iter_mut().zip(rhs.entries.iter()) {
+            *v -= *other;
         }
     }
 }
 
-impl<E: Element> Mul<&Self> for Matrix<E> {
+impl<E: Element> Sub<&Self> for Matrix<E> {
     type Output = Self;
-    fn mul(mut self, rhs: &Self) -> Self::Output {
-        self *= rhs;
+    fn sub(mut self, rhs: &Self) -> Self::Output {
+        self -= rhs;
         self
     }
 }
 
+impl<E: Element> MulAssign<&Self> for Matrix<E> {
+    fn mul_assign(&mut self, rhs: &Self) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<E: Element> Mul<&Self> for Matrix<E> {
+    type Output = Self;
+    fn mul(self, rhs: &Self) -> Self::Output {
+        &self * rhs
+    }
+}
+
+/// Matrix product of an (m, k) matrix and a (k, n) matrix, resulting in an (m, n) matrix.
+///
+/// With the `parallel` feature output rows are computed across threads.
+impl<E: Element> Mul<&Matrix<E>> for &Matrix<E> {
+    type Output = Matrix<E>;
+    fn mul(self, rhs: &Matrix<E>) -> Self::Output {
+        assert_eq!(
+            self.width, rhs.height,
+            "cannot mul matrices, lhs width does not match rhs height"
+        );
+        let mut out = Matrix::zero(self.height, rhs.width);
+        if rhs.width == 0 {
+            return out;
+        }
+        #[cfg(feature = "parallel")]
+        {
+            out.entries
+                .par_chunks_mut(MUL_ROWS_PER_TASK * rhs.width)
+                .enumerate()
+                .for_each(|(i, chunk)| self.mul_rows_into(rhs, i * MUL_ROWS_PER_TASK, chunk));
+        }
+        #[cfg(not(feature = "parallel"))]
+        self.mul_rows_into(rhs, 0, &mut out.entries);
+        out
+    }
+}
+
 impl<E: Element> Mul<&Vector<E>> for Matrix<E> {
     type Output = Vector<E>;
     fn mul(self, rhs: &Vector<E>) -> Self::Output {
-        self.entries
-            .into_iter()
-            .map(|row| (row * rhs).into_sum())
-            .collect::<Vec<_>>()
-            .into()
+        &self * rhs
     }
 }
 
+/// Matrix vector product. With the `parallel` feature rows are computed across threads.
 impl<E: Element> Mul<&Vector<E>> for &Matrix<E> {
     type Output = Vector<E>;
     fn mul(self, rhs: &Vector<E>) -> Self::Output {
-        self.entries
-            .iter()
-            .map(|row| {
-                let mut sum = E::zero();
-                for v in row.iter().zip(rhs.iter()) {
-                    sum += *v.0 * *v.1;
-                }
-                sum
-            })
-            .collect::<Vec<_>>()
-            .into()
+        assert_eq!(
+            self.width,
+            rhs.len(),
+            "cannot mul matrix by vector, matrix width does not match vector length"
+        );
+        if self.width == 0 {
+            return Vector::new(self.height);
+        }
+        #[cfg(feature = "parallel")]
+        let out = self
+            .entries
+            .par_chunks_exact(self.width)
+            .map(|row| dot(row, rhs))
+            .collect::<Vec<_>>();
+        #[cfg(not(feature = "parallel"))]
+        let out = self
+            .entries
+            .chunks_exact(self.width)
+            .map(|row| dot(row, rhs))
+            .collect::<Vec<_>>();
+        out.into()
+    }
+}
+
+/// Inner product of a matrix row and a vector.
+fn dot<E: Element>(row: &[E], v: &Vector<E>) -> E {
+    assert_eq!(row.len(), v.len(), "cannot dot vectors of different length");
+    let mut sum = E::zero();
+    for (a, b) in row.iter().zip(v.iter()) {
+        sum += *a * *b;
+    }
+    sum
+}
+
+/// Row vector product `v^T * M`, resulting in a vector of length equal to the matrix width.
+impl<E: Element> Mul<&Matrix<E>> for &Vector<E> {
+    type Output = Vector<E>;
+    fn mul(self, rhs: &Matrix<E>) -> Self::Output {
+        assert_eq!(
+            self.len(),
+            rhs.height,
+            "cannot mul vector by matrix, vector length does not match matrix height"
+        );
+        let mut out = Vector::new(rhs.width);
+        for (v, row) in self.iter().zip(rhs.iter()) {
+            if v.is_zero() {
+                continue;
+            }
+            for (out_v, row_v) in out.iter_mut().zip(row.iter()) {
+                *out_v += *v * *row_v;
+            }
+        }
+        out
+    }
+}
+
+impl<E: Element> Mul<&Matrix<E>> for Vector<E> {
+    type Output = Vector<E>;
+    fn mul(self, rhs: &Matrix<E>) -> Self::Output {
+        &self * rhs
     }
 }
 
@@ -204,7 +624,7 @@ mod test {
     #[test]
     fn matrix_identity() {
         type Field = OxfoiScalar;
-        let mut rng = rand::rng();
+        let mut rng = crate::test::test_rng();
 
         for s in 1..100 {
             let identity = Matrix::<Field>::identity(s);
@@ -216,7 +636,7 @@ mod test {
     #[test]
     fn matrix_compose_horizontal() {
         type Field = OxfoiScalar;
-        let mut rng = rand::rng();
+        let mut rng = crate::test::test_rng();
 
         let width1 = 100;
         let width2 = 50;
@@ -234,7 +654,7 @@ mod test {
     #[test]
     fn matrix_compose_vertical() {
         type Field = OxfoiScalar;
-        let mut rng = rand::rng();
+        let mut rng = crate::test::test_rng();
 
         let height1 = 100;
         let height2 = 50;
@@ -243,9 +663,287 @@ mod test {
 
         let m_composed = m1.clone().compose_vertical(m2.clone());
         assert_eq!(m_composed.height(), height1 + height2);
-        assert_eq!(m_composed.entries.len(), height1 + height2);
+        assert_eq!(m_composed.entries.len(), (height1 + height2) * 200);
         for (row_composed, row) in m_composed.iter().zip(m1.iter().chain(m2.iter())) {
             assert_eq!(row_composed, row);
         }
     }
+
+    #[test]
+    fn matrix_rank() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        for s in 1..20 {
+            assert_eq!(Matrix::<Field>::identity(s).rank(), s);
+            assert_eq!(Matrix::<Field>::zero(s, s).rank(), 0);
+            // random wide and tall matrices are full rank with overwhelming probability
+            assert_eq!(Matrix::<Field>::random(s, 2 * s, &mut rng).rank(), s);
+            assert_eq!(Matrix::<Field>::random(2 * s, s, &mut rng).rank(), s);
+        }
+
+        // duplicating rows does not increase rank
+        let m = Matrix::<Field>::random(5, 10, &mut rng);
+        assert_eq!(m.clone().compose_vertical(m).rank(), 5);
+    }
+
+    #[test]
+    fn matrix_solve() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        for (height, width) in [(10, 10), (10, 30), (30, 10)] {
+            let m = Matrix::<Field>::random(height, width, &mut rng);
+            let x = Vector::random(width, &mut rng);
+            let b = &m * &x;
+            let solution = m.solve(&b).expect("consistent system has no solution");
+            assert_eq!(&m * &solution, b);
+        }
+
+        // a tall system with a random target is inconsistent
+        let m = Matrix::<Field>::random(30, 10, &mut rng);
+        assert!(m.solve(&Vector::random(30, &mut rng)).is_none());
+    }
+
+    #[test]
+    fn matrix_kernel() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let height = 10;
+        let width = 25;
+        let m = Matrix::<Field>::random(height, width, &mut rng);
+        let kernel = m.kernel();
+        assert_eq!(kernel.len(), width - m.rank());
+        for v in &kernel {
+            assert!(!v.is_zero());
+            assert!((&m * v).is_zero());
+        }
+        // the kernel vectors are themselves linearly independent
+        let basis = Matrix::from_rows(kernel);
+        assert_eq!(basis.rank(), width - height);
+
+        assert!(Matrix::<Field>::identity(10).kernel().is_empty());
+    }
+
+    #[test]
+    fn matrix_inverse() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        for s in 1..20 {
+            let m = Matrix::<Field>::random(s, s, &mut rng);
+            let inv = m.inverse().expect("random matrix is singular");
+            let v = Vector::random(s, &mut rng);
+            assert_eq!(&m * &(&inv * &v), v);
+            assert_eq!(&inv * &(&m * &v), v);
+        }
+
+        let mut singular = Matrix::<Field>::random(5, 5, &mut rng);
+        let row = singular.row(0).to_vec();
+        singular.row_mut(4).copy_from_slice(&row);
+        assert!(singular.inverse().is_none());
+
+        let empty = Matrix::<Field>::zero(0, 0).inverse().unwrap();
+        assert_eq!(empty.dimension(), (0, 0));
+    }
+
+    #[test]
+    fn matrix_determinant() {
+        type Field = SevenScalar;
+        let mut rng = crate::test::test_rng();
+
+        for s in 1..10 {
+            assert_eq!(Matrix::<Field>::identity(s).determinant(), Field::one());
+        }
+
+        // [[1, 2], [3, 4]] has determinant -2 = 5 mod 7
+        let mut m = Matrix::<Field>::zero(2, 2);
+        m[0][0] = 1u128.into();
+        m[0][1] = 2u128.into();
+        m[1][0] = 3u128.into();
+        m[1][1] = 4u128.into();
+        assert_eq!(m.determinant(), Field::from(5u128));
+
+        // swapping rows negates the determinant
+        let mut swapped = m.clone();
+        swapped.swap_rows(0, 1);
+        assert_eq!(swapped.determinant(), Field::from(2u128));
+
+        // singular matrices have determinant zero, non-singular matrices are invertible
+        for _ in 0..100 {
+            let m = Matrix::<Field>::random(4, 4, &mut rng);
+            assert_eq!(m.determinant().is_zero(), m.inverse().is_none());
+            assert_eq!(m.determinant().is_zero(), m.rank() < 4);
+        }
+    }
+
+    #[test]
+    fn matrix_mul() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let a = Matrix::<Field>::random(10, 20, &mut rng);
+        let b = Matrix::<Field>::random(20, 30, &mut rng);
+        let ab = &a * &b;
+        assert_eq!(ab.dimension(), (10, 30));
+        // (A * B) * v = A * (B * v)
+        let v = Vector::random(30, &mut rng);
+        assert_eq!(&ab * &v, &a * &(&b * &v));
+        assert_eq!(&a * &Matrix::identity(20), a);
+        assert_eq!(&Matrix::identity(10) * &a, a);
+
+        // [[1, 2], [3, 4]] * [[5, 6], [0, 1]] = [[5, 8], [15, 22]]
+        let mut lhs = Matrix::<Field>::from_rows(vec![
+            vec![1u128.into(), 2u128.into()].into(),
+            vec![3u128.into(), 4u128.into()].into(),
+        ]);
+        let rhs = Matrix::<Field>::from_rows(vec![
+            vec![5u128.into(), 6u128.into()].into(),
+            vec![0u128.into(), 1u128.into()].into(),
+        ]);
+        let expected = Matrix::<Field>::from_rows(vec![
+            vec![5u128.into(), 8u128.into()].into(),
+            vec![15u128.into(), 22u128.into()].into(),
+        ]);
+        assert_eq!(lhs.clone() * &rhs, expected);
+        lhs *= &rhs;
+        assert_eq!(lhs, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn matrix_vector_mul_length_mismatch() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let a = Matrix::<Field>::random(10, 20, &mut rng);
+        let _ = &a * &Vector::random(19, &mut rng);
+    }
+
+    #[test]
+    fn matrix_transpose() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let a = Matrix::<Field>::random(10, 20, &mut rng);
+        let b = Matrix::<Field>::random(20, 30, &mut rng);
+        let a_t = a.transpose();
+        assert_eq!(a_t.dimension(), (20, 10));
+        assert_eq!(a_t.transpose(), a);
+        for i in 0..10 {
+            for j in 0..20 {
+                assert_eq!(a[i][j], a_t[j][i]);
+            }
+        }
+        // (A * B)^T = B^T * A^T
+        assert_eq!((&a * &b).transpose(), &b.transpose() * &a_t);
+    }
+
+    #[test]
+    fn matrix_add_sub_hadamard() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let a = Matrix::<Field>::random(10, 20, &mut rng);
+        let b = Matrix::<Field>::random(10, 20, &mut rng);
+        let sum = a.clone() + &b;
+        let diff = a.clone() - &b;
+        let product = a.clone().hadamard(&b);
+        for i in 0..10 {
+            for j in 0..20 {
+                assert_eq!(sum[i][j], a[i][j] + b[i][j]);
+                assert_eq!(diff[i][j], a[i][j] - b[i][j]);
+                assert_eq!(product[i][j], a[i][j] * b[i][j]);
+            }
+        }
+        assert_eq!(sum - &b, a);
+    }
+
+    #[test]
+    fn vector_matrix_mul() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let a = Matrix::<Field>::random(10, 20, &mut rng);
+        let c = Vector::<Field>::random(10, &mut rng);
+        let x = Vector::<Field>::random(20, &mut rng);
+        let c_t_a = &c * &a;
+        assert_eq!(c_t_a.len(), 20);
+        assert_eq!(c_t_a, &a.transpose() * &c);
+        // (c^T * A) * x = c^T * (A * x)
+        assert_eq!((c_t_a * &x).into_sum(), (c * &(&a * &x)).into_sum());
+    }
+
+    #[test]
+    fn matrix_rows_and_columns() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let rows = (0..10)
+            .map(|_| Vector::<Field>::random(20, &mut rng))
+            .collect::<Vec<_>>();
+        let m = Matrix::from_rows(rows.clone());
+        assert_eq!(m.dimension(), (10, 20));
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(m.row(i), row.iter().copied().collect::<Vec<_>>());
+        }
+        for j in 0..20 {
+            let column = m.column(j);
+            assert_eq!(column.len(), 10);
+            for (i, row) in rows.iter().enumerate() {
+                assert_eq!(column[i], row[j]);
+            }
+            assert_eq!(column.to_vector(), m.transpose().row(j).to_vec().into());
+        }
+
+        let mut swapped = m.clone();
+        swapped.swap_rows(7, 2);
+        assert_eq!(swapped.row(2), m.row(7));
+        assert_eq!(swapped.row(7), m.row(2));
+        swapped.swap_rows(2, 7);
+        assert_eq!(swapped, m);
+    }
+
+    #[test]
+    fn matrix_mul_large() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        // exercise multiple blocks of the inner dimension and multiple row chunks
+        let a = Matrix::<Field>::random(150, 200, &mut rng);
+        let b = Matrix::<Field>::random(200, 70, &mut rng);
+        let ab = &a * &b;
+        for i in (0..150).step_by(13) {
+            for j in (0..70).step_by(7) {
+                let mut expected = Field::zero();
+                for k in 0..200 {
+                    expected += a[i][k] * b[k][j];
+                }
+                assert_eq!(ab[i][j], expected);
+            }
+        }
+        let v = Vector::random(70, &mut rng);
+        assert_eq!(&ab * &v, &a * &(&b * &v));
+    }
+
+    #[test]
+    fn matrix_kron() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let a = Matrix::<Field>::random(3, 4, &mut rng);
+        let b = Matrix::<Field>::random(5, 2, &mut rng);
+        let k = a.kron(&b);
+        assert_eq!(k.dimension(), (15, 8));
+        for i in 0..15 {
+            for j in 0..8 {
+                assert_eq!(k[i][j], a[i / 5][j / 2] * b[i % 5][j % 2]);
+            }
+        }
+        // (A ⊗ B) * (x ⊗ y) = (A * x) ⊗ (B * y)
+        let x = Vector::random(4, &mut rng);
+        let y = Vector::random(2, &mut rng);
+        assert_eq!(&k * &x.kron(&y), (&a * &x).kron(&(&b * &y)));
+    }
 }
@@ -0,0 +1,446 @@
+use super::*;
+
+/// Translates a witness of an R1CS into a witness of the system produced by an optimization
+/// pass. Entry `i` of the new witness is entry `sources[i]` of the old witness.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WitnessMap {
+    sources: Vec<usize>,
+}
+
+impl WitnessMap {
+    /// The map that leaves a witness of length `width` unchanged.
+    pub fn identity(width: usize) -> Self {
+        Self {
+            sources: (0..width).collect(),
+        }
+    }
+
+    pub fn sources(&self) -> &[usize] {
+        &self.sources
+    }
+
+    /// Translate an old witness into a new witness.
+    pub fn apply<E: Element>(&self, witness: &Vector<E>) -> Vector<E> {
+        self.sources.iter().map(|i| witness[*i]).collect()
+    }
+
+    /// The new index of old variable `index`, if it was kept.
+    pub fn position(&self, index: usize) -> Option<usize> {
+        self.sources.iter().position(|i| *i == index)
+    }
+
+    /// The map applying `self` and then `next`.
+    pub fn compose(&self, next: &Self) -> Self {
+        Self {
+            sources: next.sources.iter().map(|i| self.sources[*i]).collect(),
+        }
+    }
+}
+
+/// Variables an optimization pass must keep.
+#[derive(Clone, Debug, Default)]
+pub struct OptimizeOptions {
+    /// Index of a variable fixed to one. Constraints where `A` or `B` is a multiple of this
+    /// variable are treated as linear.
+    pub one: Option<usize>,
+    /// Variables that must not be eliminated or removed, such as public inputs and outputs.
+    pub protected: Vec<usize>,
+}
+
+impl OptimizeOptions {
+    fn is_kept(&self, index: usize) -> bool {
+        self.one == Some(index) || self.protected.contains(&index)
+    }
+
+    /// Translate the kept indices through a witness map.
+    fn mapped(&self, map: &WitnessMap) -> Self {
+        Self {
+            one: self.one.and_then(|i| map.position(i)),
+            protected: self
+                .protected
+                .iter()
+                .filter_map(|i| map.position(*i))
+                .collect(),
+        }
+    }
+}
+
+/// A constraint `(a * z) * (b * z) = c * z` as dense rows.
+#[derive(Clone, PartialEq)]
+struct Constraint<E: Element> {
+    a: Vec<E>,
+    b: Vec<E>,
+    c: Vec<E>,
+}
+
+impl<E: Element> Constraint<E> {
+    /// If the constraint is linear, the row `l` such that it is equivalent to `l * z = 0`.
+    fn linear_form(&self, one: Option<usize>) -> Option<Vec<E>> {
+        let constant = |row: &[E]| -> Option<E> {
+            let mut out = E::zero();
+            for (i, v) in row.iter().enumerate() {
+                if v.is_zero() {
+                    continue;
+                }
+                if Some(i) != one {
+                    return None;
+                }
+                out = *v;
+            }
+            Some(out)
+        };
+        let (scale, other) = if let Some(scale) = constant(&self.a) {
+            (scale, &self.b)
+        } else {
+            (constant(&self.b)?, &self.a)
+        };
+        Some(
+            other
+                .iter()
+                .zip(&self.c)
+                .map(|(o, c)| scale * *o - *c)
+                .collect(),
+        )
+    }
+
+    /// Replace variable `pivot` using the linear relation `l * z = 0`.
+    fn substitute(&mut self, pivot: usize, l: &[E], pivot_inv: E) {
+        for row in [&mut self.a, &mut self.b, &mut self.c] {
+            let coef = row[pivot];
+            if coef.is_zero() {
+                continue;
+            }
+            let scale = coef * pivot_inv;
+            for (v, l) in row.iter_mut().zip(l) {
+                *v -= scale * *l;
+            }
+        }
+    }
+
+    /// The same constraint with `A` and `B` swapped, which is satisfied by the same witnesses.
+    fn swapped(&self) -> Self {
+        Self {
+            a: self.b.clone(),
+            b: self.a.clone(),
+            c: self.c.clone(),
+        }
+    }
+
+    fn is_trivial(&self) -> bool {
+        let zero = |row: &[E]| row.iter().all(|v| v.is_zero());
+        (zero(&self.a) || zero(&self.b)) && zero(&self.c)
+    }
+}
+
+impl<E: Element> R1CS<E> {
+    fn constraints(&self) -> Vec<Constraint<E>> {
+        (0..self.dimension().0)
+            .map(|i| Constraint {
+                a: self.a.row(i).to_vec(),
+                b: self.b.row(i).to_vec(),
+                c: self.c.row(i).to_vec(),
+            })
+            .collect()
+    }
+
+    /// Build an R1CS from constraints over the variables listed in `columns`.
+    fn from_constraints(constraints: &[Constraint<E>], columns: &[usize]) -> Self {
+        let mut out = Self::identity(constraints.len(), columns.len());
+        for (i, constraint) in constraints.iter().enumerate() {
+            for (j, column) in columns.iter().enumerate() {
+                out.a[i][j] = constraint.a[*column];
+                out.b[i][j] = constraint.b[*column];
+                out.c[i][j] = constraint.c[*column];
+            }
+        }
+        out
+    }
+
+    /// Run every optimization pass, returning the optimized system and a map from witnesses of
+    /// `self` to witnesses of the output.
+    pub fn optimize(&self, options: &OptimizeOptions) -> (Self, WitnessMap) {
+        let (r1cs, map) = self.eliminate_linear(options);
+        let options = options.mapped(&map);
+        let (r1cs, next) = r1cs.remove_duplicate_constraints();
+        let map = map.compose(&next);
+        let (r1cs, next) = r1cs.remove_unused_variables(&options);
+        let map = map.compose(&next);
+        let options = options.mapped(&next);
+        let (r1cs, next) = r1cs.renumber(&options);
+        (r1cs, map.compose(&next))
+    }
+
+    /// Eliminate linear constraints by solving each for its highest indexed variable and
+    /// substituting the solution into every other constraint. Eliminated variables are removed
+    /// from the witness. Linear constraints whose variables are all kept by `options` remain in
+    /// place.
+    pub fn eliminate_linear(&self, options: &OptimizeOptions) -> (Self, WitnessMap) {
+        let width = self.dimension().1;
+        let mut constraints = self.constraints().into_iter().map(Some).collect::<Vec<_>>();
+        let mut eliminated = vec![false; width];
+        let mut progress = true;
+        while progress {
+            progress = false;
+            for i in 0..constraints.len() {
+                let Some(l) = constraints[i]
+                    .as_ref()
+                    .and_then(|c| c.linear_form(options.one))
+                else {
+                    continue;
+                };
+                if l.iter().all(|v| v.is_zero()) {
+                    constraints[i] = None;
+                    progress = true;
+                    continue;
+                }
+                let Some(pivot) = (0..width)
+                    .rev()
+                    .find(|j| !l[*j].is_zero() && !options.is_kept(*j))
+                else {
+                    continue;
+                };
+                let pivot_inv = l[pivot]
+                    .inverse()
+                    .expect("pivot of a linear constraint is nonzero");
+                constraints[i] = None;
+                for constraint in constraints.iter_mut().flatten() {
+                    constraint.substitute(pivot, &l, pivot_inv);
+                }
+                eliminated[pivot] = true;
+                progress = true;
+            }
+        }
+
+        let constraints = constraints.into_iter().flatten().collect::<Vec<_>>();
+        let map = WitnessMap {
+            sources: (0..width).filter(|j| !eliminated[*j]).collect(),
+        };
+        (Self::from_constraints(&constraints, &map.sources), map)
+    }
+
+    /// Remove constraints that repeat an earlier constraint, up to swapping `A` and `B`, and
+    /// constraints that hold for every witness. The witness is unchanged.
+    pub fn remove_duplicate_constraints(&self) -> (Self, WitnessMap) {
+        let width = self.dimension().1;
+        let mut kept: Vec<Constraint<E>> = Vec::new();
+        for constraint in self.constraints() {
+            if constraint.is_trivial() {
+                continue;
+            }
+            let swapped = constraint.swapped();
+            if kept.iter().any(|k| *k == constraint || *k == swapped) {
+                continue;
+            }
+            kept.push(constraint);
+        }
+        let map = WitnessMap::identity(width);
+        (Self::from_constraints(&kept, &map.sources), map)
+    }
+
+    /// Remove variables that appear in no constraint, unless they are kept by `options`.
+    pub fn remove_unused_variables(&self, options: &OptimizeOptions) -> (Self, WitnessMap) {
+        let width = self.dimension().1;
+        let used = |j: usize| {
+            [&self.a, &self.b, &self.c]
+                .iter()
+                .any(|m| m.column(j).iter().any(|v| !v.is_zero()))
+        };
+        let map = WitnessMap {
+            sources: (0..width)
+                .filter(|j| options.is_kept(*j) || used(*j))
+                .collect(),
+        };
+        (
+            Self::from_constraints(&self.constraints(), &map.sources),
+            map,
+        )
+    }
+
+    /// Reorder the witness so the variable fixed to one comes first, followed by the protected
+    /// variables in the order given, followed by the remaining variables in their current order.
+    pub fn renumber(&self, options: &OptimizeOptions) -> (Self, WitnessMap) {
+        let width = self.dimension().1;
+        let mut sources = options.one.into_iter().collect::<Vec<_>>();
+        for i in options
+            .protected
+            .iter()
+            .chain(&(0..width).collect::<Vec<_>>())
+        {
+            if !sources.contains(i) {
+                sources.push(*i);
+            }
+        }
+        let map = WitnessMap { sources };
+        (
+            Self::from_constraints(&self.constraints(), &map.sources),
+            map,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::multiplication_r1cs;
+
+    type Field = OxfoiScalar;
+
+    /// Prepend a variable fixed to one and append linear constraints
+    /// `z[out] = 3 * z[x] + z[y] + 5`, extending a satisfying witness.
+    fn with_linear<R: Rng>(
+        r1cs: R1CS<Field>,
+        witness: Vector<Field>,
+        count: usize,
+        rng: &mut R,
+    ) -> (R1CS<Field>, Vector<Field>) {
+        let (height, width) = r1cs.dimension();
+        let mut out = R1CS::identity(height + count, width + count + 1);
+        for i in 0..height {
+            out.a[i][1..=width].copy_from_slice(r1cs.a.row(i));
+            out.b[i][1..=width].copy_from_slice(r1cs.b.row(i));
+            out.c[i][1..=width].copy_from_slice(r1cs.c.row(i));
+        }
+        let mut witness = Vector::concat([Field::one().into(), witness, Vector::new(count)]);
+        for k in 0..count {
+            let row = height + k;
+            let x = rng.random_range(1..=width + k);
+            let y = rng.random_range(1..=width + k);
+            let z = width + k + 1;
+            out.a[row][0] = Field::one();
+            out.b[row][x] += Field::from(3);
+            out.b[row][y] += Field::one();
+            out.b[row][0] += Field::from(5);
+            out.c[row][z] = Field::one();
+            witness[z] = Field::from(3) * witness[x] + witness[y] + Field::from(5);
+        }
+        (out, witness)
+    }
+
+    #[test]
+    fn witness_map_compose() {
+        let first = WitnessMap {
+            sources: vec![0, 2, 3, 5],
+        };
+        let second = WitnessMap {
+            sources: vec![3, 0, 1],
+        };
+        let witness = Vector::<Field>::random(6, &mut crate::test::test_rng());
+        assert_eq!(
+            first.compose(&second).apply(&witness),
+            second.apply(&first.apply(&witness))
+        );
+        assert_eq!(first.position(3), Some(2));
+        assert_eq!(first.position(1), None);
+    }
+
+    #[test]
+    fn eliminate_linear() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness) = multiplication_r1cs::<Field, _>(5, 12, rng);
+        let (r1cs, witness) = with_linear(r1cs, witness, 6, rng);
+        assert!(r1cs.eval(&witness)?.is_zero());
+        let options = OptimizeOptions {
+            one: Some(0),
+            protected: vec![],
+        };
+        let (optimized, map) = r1cs.eliminate_linear(&options);
+        assert_eq!(optimized.dimension(), (5, 13));
+        assert!(optimized.eval(&map.apply(&witness))?.is_zero());
+
+        // variable 8 is the output of a multiplication constraint
+        let mut bad = witness.clone();
+        bad[8] += Field::one();
+        assert!(!r1cs.eval(&bad)?.is_zero());
+        assert!(!optimized.eval(&map.apply(&bad))?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn eliminate_linear_protected() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        // z[2] = z[1] + 5 with both variables protected is kept as is
+        let mut r1cs = R1CS::<Field>::identity(1, 3);
+        r1cs.a[0][0] = Field::one();
+        r1cs.b[0][1] = Field::one();
+        r1cs.b[0][0] = Field::from(5);
+        r1cs.c[0][2] = Field::one();
+        let options = OptimizeOptions {
+            one: Some(0),
+            protected: vec![1, 2],
+        };
+        let (optimized, map) = r1cs.eliminate_linear(&options);
+        assert_eq!(map, WitnessMap::identity(3));
+        assert_eq!(optimized.dimension(), (1, 3));
+
+        let x = Field::sample_rand(rng);
+        let witness = Vector::from(vec![Field::one(), x, x + Field::from(5)]);
+        assert!(optimized.eval(&witness)?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn remove_duplicates_and_unused() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness) = multiplication_r1cs::<Field, _>(4, 12, rng);
+        let (height, width) = r1cs.dimension();
+        // duplicate every constraint with A and B swapped, and add an empty constraint
+        let mut doubled = R1CS::identity(2 * height + 1, width);
+        for i in 0..height {
+            doubled.a[i].copy_from_slice(r1cs.a.row(i));
+            doubled.b[i].copy_from_slice(r1cs.b.row(i));
+            doubled.c[i].copy_from_slice(r1cs.c.row(i));
+            doubled.a[height + i].copy_from_slice(r1cs.b.row(i));
+            doubled.b[height + i].copy_from_slice(r1cs.a.row(i));
+            doubled.c[height + i].copy_from_slice(r1cs.c.row(i));
+        }
+        let (deduped, map) = doubled.remove_duplicate_constraints();
+        assert_eq!(deduped.dimension(), (height, width));
+        assert!(deduped.eval(&map.apply(&witness))?.is_zero());
+
+        let options = OptimizeOptions {
+            one: None,
+            protected: vec![width - 1],
+        };
+        let (reduced, map) = deduped.remove_unused_variables(&options);
+        let used = (0..width)
+            .filter(|j| {
+                [&r1cs.a, &r1cs.b, &r1cs.c]
+                    .iter()
+                    .any(|m| !m.column(*j).to_vector().is_zero())
+            })
+            .count();
+        assert_eq!(reduced.dimension(), (height, used + 1));
+        assert!(map.position(width - 1).is_some());
+        assert!(reduced.eval(&map.apply(&witness))?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn optimize_pipeline() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness) = multiplication_r1cs::<Field, _>(5, 14, rng);
+        let (r1cs, witness) = with_linear(r1cs, witness, 8, rng);
+        let width = r1cs.dimension().1;
+        let options = OptimizeOptions {
+            one: Some(0),
+            protected: vec![width - 1],
+        };
+        let (optimized, map) = r1cs.optimize(&options);
+        assert!(optimized.dimension().0 <= 5);
+        assert!(optimized.dimension().1 < width);
+        assert_eq!(map.sources()[0], 0);
+        assert_eq!(map.sources()[1], width - 1);
+        assert!(optimized.eval(&map.apply(&witness))?.is_zero());
+
+        // random witnesses for the optimized system fail with overwhelming probability
+        for _ in 0..10 {
+            let random = Vector::random(width, rng);
+            assert!(!optimized.eval(&map.apply(&random))?.is_zero());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+mod multilinear;
+
+pub use multilinear::*;
@@ -0,0 +1,227 @@
+use crate::*;
+
+/// A multilinear polynomial in `num_vars` variables, stored densely as its evaluations over the
+/// boolean hypercube {0, 1}^num_vars.
+///
+/// The evaluation at (x_0, ..., x_{n-1}) is stored at index `sum(x_k * 2^k)`, so the first
+/// variable selects between adjacent entries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultilinearPolynomial<E: Element> {
+    num_vars: usize,
+    evaluations: Vector<E>,
+}
+
+impl<E: Element> MultilinearPolynomial<E> {
+    /// Build the multilinear extension of a vector of evaluations.
+    ///
+    /// Panics if the length of `evaluations` is not a power of two.
+    pub fn new(evaluations: Vector<E>) -> Self {
+        assert!(
+            evaluations.len().is_power_of_two(),
+            "MultilinearPolynomial::new evaluation count {} is not a power of two",
+            evaluations.len()
+        );
+        Self {
+            num_vars: evaluations.len().trailing_zeros() as usize,
+            evaluations,
+        }
+    }
+
+    /// Build the multilinear extension of a vector padded with zeros to the next power of two.
+    pub fn from_vector_padded(mut values: Vector<E>) -> Self {
+        let len = values.len().next_power_of_two();
+        values.append(Vector::new(len - values.len()));
+        Self::new(values)
+    }
+
+    /// The equality polynomial `eq(point, x) = prod_k (point_k * x_k + (1 - point_k) * (1 - x_k))`
+    /// as a multilinear polynomial in `x`. It is 1 at `x = point` on the hypercube and 0
+    /// elsewhere.
+    pub fn eq(point: &[E]) -> Self {
+        let mut evaluations = vec![E::one()];
+        for r in point {
+            let len = evaluations.len();
+            evaluations.resize(2 * len, E::zero());
+            for i in 0..len {
+                let high = evaluations[i] * *r;
+                evaluations[i + len] = high;
+                evaluations[i] -= high;
+            }
+        }
+        Self::new(evaluations.into())
+    }
+
+    /// Evaluate `eq(a, b)` directly.
+    ///
+    /// Panics if the points are not of equal length.
+    pub fn eq_evaluate(a: &[E], b: &[E]) -> E {
+        assert_eq!(
+            a.len(),
+            b.len(),
+            "MultilinearPolynomial::eq_evaluate points of unequal length"
+        );
+        let mut out = E::one();
+        for (a, b) in a.iter().zip(b) {
+            out *= *a * *b + (E::one() - *a) * (E::one() - *b);
+        }
+        out
+    }
+
+    pub fn random<R: Rng>(num_vars: usize, rng: &mut R) -> Self {
+        Self::new(Vector::random(1 << num_vars, rng))
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Evaluations over the boolean hypercube.
+    pub fn evaluations(&self) -> &Vector<E> {
+        &self.evaluations
+    }
+
+    /// Fix the first variable to `r`, resulting in a polynomial in `num_vars - 1` variables.
+    pub fn bind(&self, r: E) -> Self {
+        let mut out = self.clone();
+        out.bind_in_place(r);
+        out
+    }
+
+    /// Fix the first variable to `r` in place.
+    ///
+    /// Panics if the polynomial has no variables left.
+    pub fn bind_in_place(&mut self, r: E) {
+        assert!(
+            self.num_vars > 0,
+            "MultilinearPolynomial::bind polynomial is constant"
+        );
+        let half = self.evaluations.len() / 2;
+        let mut out = Vec::with_capacity(half);
+        for pair in self.evaluations.chunks(2) {
+            out.push(pair[0] + r * (pair[1] - pair[0]));
+        }
+        self.evaluations = out.into();
+        self.num_vars -= 1;
+    }
+
+    /// Evaluate the polynomial at an arbitrary point.
+    ///
+    /// Panics if the point is not of length `num_vars`.
+    pub fn evaluate(&self, point: &[E]) -> E {
+        assert_eq!(
+            point.len(),
+            self.num_vars,
+            "MultilinearPolynomial::evaluate point has wrong number of variables"
+        );
+        let Some((first, rest)) = point.split_first() else {
+            return self.evaluations[0];
+        };
+        let mut bound = self.bind(*first);
+        for r in rest {
+            bound.bind_in_place(*r);
+        }
+        bound.evaluations[0]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mle_evaluate_hypercube() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let num_vars = 5;
+        let poly = MultilinearPolynomial::<Field>::random(num_vars, &mut rng);
+        for i in 0..(1 << num_vars) {
+            let point = (0..num_vars)
+                .map(|k| Field::from(((i >> k) & 1) as u128))
+                .collect::<Vec<_>>();
+            assert_eq!(poly.evaluate(&point), poly.evaluations()[i]);
+        }
+    }
+
+    #[test]
+    fn mle_multilinear() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let num_vars = 6;
+        let poly = MultilinearPolynomial::<Field>::random(num_vars, &mut rng);
+        let point = Vector::<Field>::random(num_vars, &mut rng);
+        // the polynomial is affine in each variable: p(.., t, ..) = p(.., 0, ..) + t * (p(.., 1, ..) - p(.., 0, ..))
+        for k in 0..num_vars {
+            let mut at = point.clone();
+            at[k] = Field::zero();
+            let zero = poly.evaluate(&at[..]);
+            at[k] = Field::one();
+            let one = poly.evaluate(&at[..]);
+            assert_eq!(poly.evaluate(&point[..]), zero + point[k] * (one - zero));
+        }
+    }
+
+    #[test]
+    fn mle_bind() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let num_vars = 4;
+        let poly = MultilinearPolynomial::<Field>::random(num_vars, &mut rng);
+        let point = Vector::<Field>::random(num_vars, &mut rng);
+        let bound = poly.bind(point[0]);
+        assert_eq!(bound.num_vars(), num_vars - 1);
+        assert_eq!(bound.evaluate(&point[1..]), poly.evaluate(&point[..]));
+
+        let constant = MultilinearPolynomial::new(Field::from(9u128).into());
+        assert_eq!(constant.num_vars(), 0);
+        assert_eq!(constant.evaluate(&[]), Field::from(9u128));
+    }
+
+    #[test]
+    fn mle_eq() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let num_vars = 4;
+        let point = Vector::<Field>::random(num_vars, &mut rng);
+        let eq = MultilinearPolynomial::eq(&point[..]);
+        assert_eq!(eq.num_vars(), num_vars);
+        // eq is the multilinear extension of the point evaluated on the hypercube
+        let other = Vector::<Field>::random(num_vars, &mut rng);
+        assert_eq!(
+            eq.evaluate(&other[..]),
+            MultilinearPolynomial::eq_evaluate(&point[..], &other[..])
+        );
+        // <eq(r, .), f> = f(r) for any multilinear f
+        let f = MultilinearPolynomial::<Field>::random(num_vars, &mut rng);
+        assert_eq!(
+            eq.evaluations().inner(f.evaluations()),
+            f.evaluate(&point[..])
+        );
+        // on the hypercube eq is an indicator
+        let boolean = [Field::one(), Field::zero(), Field::one(), Field::one()];
+        let eq = MultilinearPolynomial::eq(&boolean);
+        for (i, v) in eq.evaluations().iter().enumerate() {
+            assert_eq!(*v == Field::one(), i == 0b1101);
+            assert_eq!(v.is_zero(), i != 0b1101);
+        }
+    }
+
+    #[test]
+    fn mle_padded() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let values = Vector::<Field>::random(5, &mut rng);
+        let poly = MultilinearPolynomial::from_vector_padded(values.clone());
+        assert_eq!(poly.num_vars(), 3);
+        assert_eq!(&poly.evaluations()[..5], values.as_slice());
+        assert!(Vector::from(&poly.evaluations()[5..]).is_zero());
+        assert_eq!(
+            MultilinearPolynomial::from_vector_padded(values.clone()).evaluations(),
+            MultilinearPolynomial::from_vector_padded(poly.evaluations().clone()).evaluations()
+        );
+    }
+}
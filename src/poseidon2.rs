@@ -0,0 +1,451 @@
+use super::*;
+
+/// State width of the permutation.
+pub const POSEIDON2_WIDTH: usize = 8;
+/// Number of state elements absorbed per permutation by `Poseidon2Hasher`. The remaining 4
+/// elements, 256 bits, are capacity.
+pub const POSEIDON2_RATE: usize = 4;
+/// S-box exponent, the smallest `d` with `gcd(d, p - 1) = 1`.
+const SBOX_DEGREE: u128 = 7;
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 22;
+
+/// `D` in `M_I = J + D` from the reference Goldilocks parameters (`MAT_DIAG8_M_1`).
+const INTERNAL_DIAGONAL: [u64; POSEIDON2_WIDTH] = [
+    0xa98811a1fed4e3a5,
+    0x1cc48b54f377e2a0,
+    0xe40cd4f6c5609a26,
+    0x11de79ebca97a4a3,
+    0x9177c73d8b7e929c,
+    0x2a6fe8085797e791,
+    0x3de6e93329f8d5ad,
+    0x3f7af9125da962fe,
+];
+
+static POSEIDON2: LazyLock<Poseidon2> = LazyLock::new(Poseidon2::generate);
+
+/// The Poseidon2 permutation over `OxfoiScalar` (Goldilocks) with width 8, S-box `x^7`, 8 full
+/// rounds and 22 partial rounds.
+///
+/// https://eprint.iacr.org/2023/323.pdf
+///
+/// Round constants are sampled from the Grain LFSR of the Poseidon reference in round order and
+/// the internal diagonal is the one published with the reference Goldilocks parameters, so this
+/// is the reference width 8 instance.
+#[derive(Clone, Debug)]
+pub struct Poseidon2 {
+    /// Constants added to every state element in each full round.
+    pub external_constants: Vec<[OxfoiScalar; POSEIDON2_WIDTH]>,
+    /// Constants added to the first state element in each partial round.
+    pub internal_constants: Vec<OxfoiScalar>,
+    /// `D` in the internal matrix `M_I = J + D`, where `J` is the all ones matrix.
+    pub internal_diagonal: [OxfoiScalar; POSEIDON2_WIDTH],
+}
+
+impl Poseidon2 {
+    /// The shared instance of the permutation.
+    pub fn get() -> &'static Self {
+        &POSEIDON2
+    }
+
+    fn generate() -> Self {
+        let mut grain = GrainLFSR::new(
+            OxfoiScalar::BIT_WIDTH,
+            POSEIDON2_WIDTH,
+            FULL_ROUNDS,
+            PARTIAL_ROUNDS,
+        );
+        // constants are drawn in round order, one per state element in full rounds and one per
+        // partial round
+        let full_rounds = |grain: &mut GrainLFSR| {
+            (0..FULL_ROUNDS / 2)
+                .map(|_| std::array::from_fn(|_| grain.next_element()))
+                .collect::<Vec<_>>()
+        };
+        let mut external_constants = full_rounds(&mut grain);
+        let internal_constants = (0..PARTIAL_ROUNDS)
+            .map(|_| grain.next_element())
+            .collect::<Vec<_>>();
+        external_constants.extend(full_rounds(&mut grain));
+        let internal_diagonal = INTERNAL_DIAGONAL.map(|d| OxfoiScalar::from(d as u128));
+
+        Self {
+            external_constants,
+            internal_constants,
+            internal_diagonal,
+        }
+    }
+
+    /// Apply the permutation in place.
+    pub fn permute(&self, state: &mut [OxfoiScalar; POSEIDON2_WIDTH]) {
+        Self::external_linear_layer(state);
+        let (first, last) = self.external_constants.split_at(FULL_ROUNDS / 2);
+        for constants in first {
+            self.full_round(state, constants);
+        }
+        for constant in &self.internal_constants {
+            state[0] += *constant;
+            state[0] = state[0].pow(SBOX_DEGREE);
+            self.internal_linear_layer(state);
+        }
+        for constants in last {
+            self.full_round(state, constants);
+        }
+    }
+
+    /// `M_E` as an explicit matrix.
+    pub fn external_matrix() -> Matrix<OxfoiScalar> {
+        Self::layer_matrix(Self::external_linear_layer)
+    }
+
+    /// `M_I` as an explicit matrix.
+    pub fn internal_matrix(&self) -> Matrix<OxfoiScalar> {
+        Self::layer_matrix(|state| self.internal_linear_layer(state))
+    }
+
+    fn layer_matrix(layer: impl Fn(&mut [OxfoiScalar; POSEIDON2_WIDTH])) -> Matrix<OxfoiScalar> {
+        let mut out = Matrix::zero(POSEIDON2_WIDTH, POSEIDON2_WIDTH);
+        for j in 0..POSEIDON2_WIDTH {
+            let mut column = [OxfoiScalar::zero(); POSEIDON2_WIDTH];
+            column[j] = OxfoiScalar::one();
+            layer(&mut column);
+            for (i, v) in column.iter().enumerate() {
+                out[i][j] = *v;
+            }
+        }
+        out
+    }
+
+    /// Hash a sequence of elements with the sponge used by `Poseidon2Hasher`.
+    pub fn hash(&self, input: &[OxfoiScalar]) -> OxfoiScalar {
+        let mut hasher = Poseidon2Hasher::default();
+        for v in input {
+            hasher.write_element(v);
+        }
+        hasher.finish()
+    }
+
+    fn full_round(
+        &self,
+        state: &mut [OxfoiScalar; POSEIDON2_WIDTH],
+        constants: &[OxfoiScalar; POSEIDON2_WIDTH],
+    ) {
+        for (v, c) in state.iter_mut().zip(constants) {
+            *v = (*v + *c).pow(SBOX_DEGREE);
+        }
+        Self::external_linear_layer(state);
+    }
+
+    /// Multiply by `M_E = circ(2 * M_4, M_4)`, applying `M_4` to each half and adding the sum
+    /// of the halves.
+    pub fn external_linear_layer(state: &mut [OxfoiScalar; POSEIDON2_WIDTH]) {
+        for chunk in state.chunks_exact_mut(4) {
+            let out = Self::m4(chunk);
+            chunk.copy_from_slice(&out);
+        }
+        for i in 0..4 {
+            let sum = state[i] + state[i + 4];
+            state[i] += sum;
+            state[i + 4] += sum;
+        }
+    }
+
+    /// Multiply by `M_I = J + D`.
+    pub fn internal_linear_layer(&self, state: &mut [OxfoiScalar; POSEIDON2_WIDTH]) {
+        let sum = state[1..].iter().fold(state[0], |acc, v| acc + *v);
+        for (v, d) in state.iter_mut().zip(&self.internal_diagonal) {
+            *v = sum + *v * *d;
+        }
+    }
+
+    /// The 4x4 MDS matrix from the Poseidon2 paper,
+    /// `[[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]]`.
+    fn m4(x: &[OxfoiScalar]) -> [OxfoiScalar; 4] {
+        const M4: [[u128; 4]; 4] = [[5, 7, 1, 3], [4, 6, 1, 1], [1, 3, 5, 7], [1, 1, 4, 6]];
+        std::array::from_fn(|i| {
+            (1..4).fold(x[0] * OxfoiScalar::from(M4[i][0]), |acc, j| {
+                acc + x[j] * OxfoiScalar::from(M4[i][j])
+            })
+        })
+    }
+}
+
+/// The Grain LFSR used to generate Poseidon parameters, Appendix F of
+/// https://eprint.iacr.org/2019/458.pdf
+struct GrainLFSR {
+    state: [bool; 80],
+}
+
+impl GrainLFSR {
+    fn new(field_bits: usize, width: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        let mut init = Vec::with_capacity(80);
+        let mut push = |value: usize, len: usize| {
+            for i in (0..len).rev() {
+                init.push((value >> i) & 1 == 1);
+            }
+        };
+        // prime field, x^d S-box
+        push(1, 2);
+        push(0, 4);
+        push(field_bits, 12);
+        push(width, 12);
+        push(full_rounds, 10);
+        push(partial_rounds, 10);
+        push((1 << 30) - 1, 30);
+        let mut out = Self {
+            state: init.try_into().expect("Grain LFSR state is 80 bits"),
+        };
+        for _ in 0..160 {
+            out.next_raw();
+        }
+        out
+    }
+
+    fn next_raw(&mut self) -> bool {
+        let s = &self.state;
+        let bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+        self.state.rotate_left(1);
+        self.state[79] = bit;
+        bit
+    }
+
+    /// Output bits with the self shrinking generator.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.next_raw();
+            let bit = self.next_raw();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// Sample a field element most significant bit first, rejecting values that are out of
+    /// range.
+    fn next_element(&mut self) -> OxfoiScalar {
+        loop {
+            let v = (0..OxfoiScalar::BIT_WIDTH)
+                .fold(0u128, |acc, _| (acc << 1) | self.next_bit() as u128);
+            if v < OxfoiScalar::CARDINALITY {
+                return OxfoiScalar::from(v);
+            }
+        }
+    }
+}
+
+/// A sponge over `Poseidon2` implementing `ElementHasher<OxfoiScalar>`.
+///
+/// Elements are absorbed directly, so a transcript of elements hashes to the same value as
+/// `Poseidon2::hash` and the `poseidon2_hash` gadget. Bytes are absorbed in chunks of
+/// 7, and a final partial chunk of `n` bytes has `n` in its eighth byte so it never matches a
+/// full chunk. Byte chunks are separated from elements of the same value by marking the rate
+/// positions they occupy in the last capacity element before each permutation. `finish` pads
+/// with a single 1 element and squeezes the first state element.
+#[derive(Clone, Debug, Default)]
+pub struct Poseidon2Hasher {
+    state: [OxfoiScalar; POSEIDON2_WIDTH],
+    position: usize,
+    /// Bit `i` is set if rate position `i` of the current block was absorbed from bytes.
+    byte_positions: u8,
+    pending: Vec<u8>,
+}
+
+impl Poseidon2Hasher {
+    const BYTES_PER_ELEMENT: usize = 7;
+
+    fn absorb(&mut self, v: OxfoiScalar, from_bytes: bool) {
+        self.state[self.position] += v;
+        self.byte_positions |= (from_bytes as u8) << self.position;
+        self.position += 1;
+        if self.position == POSEIDON2_RATE {
+            self.permute();
+        }
+    }
+
+    fn permute(&mut self) {
+        self.state[POSEIDON2_WIDTH - 1] += OxfoiScalar::from(self.byte_positions as u128);
+        Poseidon2::get().permute(&mut self.state);
+        self.position = 0;
+        self.byte_positions = 0;
+    }
+
+    /// Absorb buffered bytes that do not fill an element.
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let len = self.pending.len();
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.resize(Self::BYTES_PER_ELEMENT, 0);
+        bytes.push(len as u8);
+        self.absorb(bytes_to_element(&bytes), true);
+    }
+}
+
+fn bytes_to_element(bytes: &[u8]) -> OxfoiScalar {
+    let mut buf = [0u8; 16];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    OxfoiScalar::from(u128::from_le_bytes(buf))
+}
+
+impl ElementHasher<OxfoiScalar> for Poseidon2Hasher {
+    fn finish(&self) -> OxfoiScalar {
+        let mut out = self.clone();
+        out.flush();
+        out.state[out.position] += OxfoiScalar::one();
+        out.permute();
+        out.state[0]
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.pending.push(*byte);
+            if self.pending.len() == Self::BYTES_PER_ELEMENT {
+                let v = bytes_to_element(&std::mem::take(&mut self.pending));
+                self.absorb(v, true);
+            }
+        }
+    }
+
+    fn write_element(&mut self, element: &OxfoiScalar) {
+        self.flush();
+        self.absorb(*element, false);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn poseidon2_constants() {
+        // first round constants and first partial round constant of the reference parameters
+        let permutation = Poseidon2::get();
+        let external = [
+            0xdd5743e7f2a5a5d9u128,
+            0xcb3a864e58ada44b,
+            0xffa2449ed32f8cdc,
+        ];
+        for (c, expected) in permutation.external_constants[0].iter().zip(external) {
+            assert_eq!(*c, OxfoiScalar::from(expected));
+        }
+        assert_eq!(
+            permutation.internal_constants[0],
+            OxfoiScalar::from(0x488897d85ff51f56u128)
+        );
+        assert!(permutation.internal_matrix().inverse().is_some());
+    }
+
+    #[test]
+    fn poseidon2_permutation() {
+        let rng = &mut crate::test::test_rng();
+
+        let permutation = Poseidon2::get();
+        assert_eq!(permutation.external_constants.len(), FULL_ROUNDS);
+        assert_eq!(permutation.internal_constants.len(), PARTIAL_ROUNDS);
+
+        let input: [OxfoiScalar; POSEIDON2_WIDTH] =
+            std::array::from_fn(|_| OxfoiScalar::sample_rand(rng));
+        let mut a = input;
+        let mut b = input;
+        permutation.permute(&mut a);
+        permutation.permute(&mut b);
+        assert_eq!(a, b);
+        assert_ne!(a, input);
+
+        // every output depends on every input
+        for i in 0..POSEIDON2_WIDTH {
+            let mut other = input;
+            other[i] += OxfoiScalar::one();
+            permutation.permute(&mut other);
+            for j in 0..POSEIDON2_WIDTH {
+                assert_ne!(other[j], a[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn poseidon2_linear_layers() {
+        let rng = &mut crate::test::test_rng();
+
+        let x: [OxfoiScalar; POSEIDON2_WIDTH] =
+            std::array::from_fn(|_| OxfoiScalar::sample_rand(rng));
+        let m_e = Poseidon2::external_matrix();
+        let m_i = Poseidon2::get().internal_matrix();
+        let mut e = x;
+        Poseidon2::external_linear_layer(&mut e);
+        assert_eq!(&m_e * &Vector::from(x.to_vec()), Vector::from(e.to_vec()));
+        let mut i = x;
+        Poseidon2::get().internal_linear_layer(&mut i);
+        assert_eq!(&m_i * &Vector::from(x.to_vec()), Vector::from(i.to_vec()));
+
+        // M_E is built from 2 * M_4 on the diagonal blocks and M_4 off the diagonal
+        assert_eq!(m_e[0][..4], [10, 14, 2, 6].map(OxfoiScalar::from));
+        assert_eq!(m_e[0][4..], [5, 7, 1, 3].map(OxfoiScalar::from));
+        assert!(m_e.inverse().is_some());
+        assert!(m_i.inverse().is_some());
+    }
+
+    #[test]
+    fn poseidon2_hasher() {
+        let rng = &mut crate::test::test_rng();
+
+        let input = Vector::<OxfoiScalar>::random(11, rng);
+        let mut hasher = Poseidon2Hasher::default();
+        for v in input.iter() {
+            hasher.write_element(v);
+        }
+        assert_eq!(hasher.finish(), Poseidon2::get().hash(input.as_slice()));
+        assert_ne!(
+            Poseidon2::get().hash(&input[..10]),
+            Poseidon2::get().hash(input.as_slice())
+        );
+        // a trailing zero element is not ignored
+        assert_ne!(
+            Poseidon2::get().hash(&[OxfoiScalar::one()]),
+            Poseidon2::get().hash(&[OxfoiScalar::one(), OxfoiScalar::zero()])
+        );
+
+        let mut a = Poseidon2Hasher::default();
+        let mut b = Poseidon2Hasher::default();
+        a.write(b"hello world");
+        b.write(b"hello world!");
+        assert_ne!(
+            ElementHasher::<OxfoiScalar>::finish(&a),
+            ElementHasher::<OxfoiScalar>::finish(&b)
+        );
+        // a partial chunk differs from a full chunk with the same leading bytes
+        let mut a = Poseidon2Hasher::default();
+        let mut b = Poseidon2Hasher::default();
+        a.write(&[5]);
+        b.write(&[5, 0, 0, 0, 0, 0, 0]);
+        assert_ne!(
+            ElementHasher::<OxfoiScalar>::finish(&a),
+            ElementHasher::<OxfoiScalar>::finish(&b)
+        );
+
+        // bytes never hash like the element they encode
+        let element = |v: u128| {
+            let mut hasher = Poseidon2Hasher::default();
+            hasher.write_element(&OxfoiScalar::from(v));
+            ElementHasher::<OxfoiScalar>::finish(&hasher)
+        };
+        let mut bytes = Poseidon2Hasher::default();
+        bytes.write(&[5]);
+        assert_ne!(
+            ElementHasher::<OxfoiScalar>::finish(&bytes),
+            element(5 | (1 << 56))
+        );
+        let chunk = [1, 2, 3, 4, 5, 6, 7];
+        let mut bytes = Poseidon2Hasher::default();
+        bytes.write(&chunk);
+        assert_ne!(
+            ElementHasher::<OxfoiScalar>::finish(&bytes),
+            element(bytes_to_element(&chunk).into())
+        );
+
+        let first: OxfoiScalar = a.challenge();
+        let second: OxfoiScalar = a.challenge();
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::*;
+
+/// Error in the last place of the f64 arithmetic used by the analysis, and by `GaussianCDT::new`.
+const F64_ROUNDING: f64 = f64::EPSILON / 2.0;
+
+/// Precision of a `GaussianCDT` compared to the ideal discrete Gaussian `D_{Z, sigma}`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CdtPrecision {
+    /// Mass of the ideal distribution beyond the largest displacement in the table.
+    pub tail_mass: f64,
+    /// Statistical distance between the fixed point table and the ideal distribution, including
+    /// the tail.
+    pub statistical_distance: f64,
+    /// Bound on the error of `statistical_distance` from evaluating the ideal distribution in
+    /// f64. Distances below this are indistinguishable from zero.
+    pub numerical_error: f64,
+}
+
+impl CdtPrecision {
+    /// Bits of security implied by the statistical distance, `-log2(max(distance, error))`.
+    pub fn security_bits(&self) -> f64 {
+        -self.statistical_distance.max(self.numerical_error).log2()
+    }
+}
+
+/// Smallest tail cut and fixed point precision for which a CDT is within statistical distance
+/// `2^-security_bits` of `D_{Z, sigma}`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CdtParameters {
+    /// Largest displacement to include in the table.
+    pub tail_cut: u128,
+    /// Tail cut as a multiple of sigma, comparable to `TAIL_BOUND_MULTIPLIER`.
+    pub tail_multiplier: f64,
+    /// Bits of precision needed in each cumulative probability. Rounding each of the
+    /// `2 * tail_cut` boundaries by at most `2^-precision_bits` moves at most
+    /// `2 * tail_cut * 2^-precision_bits` of mass.
+    pub precision_bits: u32,
+}
+
+impl CdtParameters {
+    /// Pick parameters so that both the tail mass and the rounding error are below
+    /// `2^-(security_bits + 1)`.
+    ///
+    /// `GaussianCDT` stores 64 bit fixed point values computed in f64, which supports about
+    /// 45 bits of security for small sigma. Larger targets need a table built with more precise
+    /// arithmetic.
+    pub fn for_security(sigma: f64, security_bits: u32) -> Self {
+        assert!(sigma > 0.0, "CdtParameters: sigma must be positive");
+        let target = -((security_bits + 1) as f64) * std::f64::consts::LN_2;
+        let mut tail_cut = 1u128;
+        while ln_tail_mass(sigma, tail_cut) > target {
+            tail_cut += 1;
+        }
+        let boundaries = (2 * tail_cut) as f64;
+        let precision_bits = security_bits + 1 + boundaries.log2().ceil() as u32;
+        Self {
+            tail_cut,
+            tail_multiplier: tail_cut as f64 / sigma,
+            precision_bits,
+        }
+    }
+}
+
+/// Unnormalized log density of the discrete Gaussian, `-x^2 / (2 sigma^2)`.
+fn ln_rho(sigma: f64, x: i64) -> f64 {
+    -(x as f64).powi(2) / (2.0 * sigma * sigma)
+}
+
+/// Log of the normalizer `sum_{x in Z} rho(x)`. Terms beyond `40 sigma` are below `e^-800`.
+fn ln_normalizer(sigma: f64) -> f64 {
+    let bound = (40.0 * sigma).ceil() as i64 + 1;
+    (-bound..=bound)
+        .map(|x| ln_rho(sigma, x).exp())
+        .sum::<f64>()
+        .ln()
+}
+
+/// Log of the ideal mass `P(|x| > tail_cut)`, computed in the log domain so tiny tails do not
+/// underflow.
+fn ln_tail_mass(sigma: f64, tail_cut: u128) -> f64 {
+    let start = tail_cut as i64 + 1;
+    let first = ln_rho(sigma, start);
+    let mut sum = 0f64;
+    let mut x = start;
+    loop {
+        let relative = ln_rho(sigma, x) - first;
+        if relative < -50.0 {
+            break;
+        }
+        sum += relative.exp();
+        x += 1;
+    }
+    // both tails
+    std::f64::consts::LN_2 + first + sum.ln() - ln_normalizer(sigma)
+}
+
+impl GaussianCDT {
+    /// Probability of each displacement when sampling with the fixed point table, as used by
+    /// `sample_ct`.
+    pub fn realized_probabilities(&self) -> Vec<(i32, f64)> {
+        let mut previous = 0u128;
+        let mut out = Vec::with_capacity(self.displacements.len());
+        for (i, (_, disp)) in self.displacements.iter().enumerate() {
+            let cumulative = self
+                .fixed_point
+                .get(i)
+                .map(|v| *v as u128)
+                .unwrap_or(1 << 64);
+            out.push((*disp, (cumulative - previous) as f64 / 2f64.powi(64)));
+            previous = cumulative;
+        }
+        out
+    }
+
+    /// Probability of `disp` under the ideal discrete Gaussian with this table's sigma.
+    pub fn ideal_probability(&self, disp: i32) -> f64 {
+        (ln_rho(self.sigma, disp as i64) - ln_normalizer(self.sigma)).exp()
+    }
+
+    /// Compare the fixed point table with `D_{Z, sigma}`.
+    pub fn precision(&self) -> CdtPrecision {
+        let tail_mass = ln_tail_mass(self.sigma, self.tail_bound()).exp();
+        let mut distance = tail_mass;
+        for (disp, realized) in self.realized_probabilities() {
+            distance += (realized - self.ideal_probability(disp)).abs();
+        }
+        // each probability is off by a few ulps, a few ulps of the total mass of 1
+        let numerical_error = 8.0 * self.displacements.len() as f64 * F64_ROUNDING;
+        let statistical_distance = distance / 2.0;
+        log::info!(
+            "CDT sigma {}: statistical distance {statistical_distance:e}, tail mass {tail_mass:e}",
+            self.sigma
+        );
+        CdtPrecision {
+            tail_mass,
+            statistical_distance,
+            numerical_error,
+        }
+    }
+
+    /// Statistical distance between the fixed point table and `D_{Z, sigma}`.
+    pub fn statistical_distance(&self) -> f64 {
+        self.precision().statistical_distance
+    }
+
+    /// Natural log of the Renyi divergence `R_a(P || Q)` of the fixed point table `P` from the
+    /// ideal distribution `Q`, for order `a > 1`. An infinite order gives the max-log of
+    /// `P(x) / Q(x)`.
+    ///
+    /// The divergence is `(sum_x P(x)^a / Q(x)^(a - 1))^(1 / (a - 1))`, which is 1 for identical
+    /// distributions, so the log is returned to keep precision near 0. The tail only shrinks the
+    /// support of `P` and is accounted for by the renormalization of the table. The result is
+    /// accurate to about `numerical_error` of `precision`.
+    pub fn renyi_divergence_ln(&self, order: f64) -> f64 {
+        assert!(order > 1.0, "Renyi divergence order must be greater than 1");
+        let ratios = self
+            .realized_probabilities()
+            .into_iter()
+            .filter(|(_, realized)| *realized > 0.0)
+            .map(|(disp, realized)| {
+                let ideal = self.ideal_probability(disp);
+                (realized, (realized - ideal) / ideal)
+            })
+            .collect::<Vec<_>>();
+        if order.is_infinite() {
+            return ratios
+                .iter()
+                .map(|(_, delta)| delta.ln_1p())
+                .fold(f64::NEG_INFINITY, f64::max);
+        }
+        // sum_x P(x) * ((P(x) / Q(x))^(a - 1) - 1), with P(x) / Q(x) = 1 + delta
+        let excess = ratios
+            .iter()
+            .map(|(realized, delta)| realized * ((order - 1.0) * delta.ln_1p()).exp_m1())
+            .sum::<f64>();
+        excess.ln_1p() / (order - 1.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn cdt_precision() {
+        for sigma in [1.0, 3.2, 10.0, 25.0] {
+            let cdt = GaussianCDT::new::<Field>(sigma);
+            let realized = cdt.realized_probabilities();
+            assert_eq!(realized.len(), cdt.displacements.len());
+            assert!((realized.iter().map(|(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-15);
+
+            let precision = cdt.precision();
+            // a tail cut at 8 sigma leaves less than 2^-49 of the mass
+            assert!(precision.tail_mass > 0.0 && precision.tail_mass < 2f64.powi(-49));
+            assert!(precision.statistical_distance >= precision.tail_mass / 2.0);
+            assert!(precision.security_bits() > 40.0, "{precision:?}");
+
+            let r_2 = cdt.renyi_divergence_ln(2.0);
+            let r_64 = cdt.renyi_divergence_ln(64.0);
+            let r_inf = cdt.renyi_divergence_ln(f64::INFINITY);
+            assert!(r_2.abs() < 1e-12);
+            // entries deep in the tail are rounded to multiples of 2^-64, so their relative
+            // error, and the max-log divergence, is large
+            assert!(r_inf.is_finite() && r_inf > 0.0);
+            // Renyi divergence is nondecreasing in the order
+            assert!(r_2 <= r_64 + precision.numerical_error);
+            assert!(r_64 <= r_inf + precision.numerical_error);
+        }
+    }
+
+    #[test]
+    fn cdt_precision_detects_perturbation() {
+        let sigma = 3.0;
+        let cdt = GaussianCDT::new::<Field>(sigma);
+        let baseline = cdt.precision().statistical_distance;
+        // move 2^-20 of the mass from displacement 0 to displacement -1
+        let mut fixed_point = cdt.fixed_point.clone();
+        let center = fixed_point.len() / 2;
+        fixed_point[center - 1] += 1 << 44;
+        let perturbed = GaussianCDT {
+            cardinality: cdt.cardinality,
+            sigma,
+            displacements: cdt.displacements.clone(),
+            normalized_sum: cdt.normalized_sum,
+            fixed_point,
+        };
+        let distance = perturbed.statistical_distance();
+        assert!((distance - baseline - 2f64.powi(-20)).abs() < 1e-12);
+        // P(-1) / Q(-1) grew by about 2^-20 / Q(-1)
+        let expected = (2f64.powi(-20) / perturbed.ideal_probability(-1)).ln_1p();
+        assert!(perturbed.renyi_divergence_ln(f64::INFINITY) >= expected * (1.0 - 1e-6));
+        // R_2 grows by about (2^-20)^2 * (1 / Q(-1) + 1 / Q(0))
+        let growth = perturbed.renyi_divergence_ln(2.0) - cdt.renyi_divergence_ln(2.0);
+        let expected = 2f64.powi(-40)
+            * (1.0 / perturbed.ideal_probability(-1) + 1.0 / perturbed.ideal_probability(0));
+        assert!((growth - expected).abs() < 0.01 * expected);
+    }
+
+    #[test]
+    fn cdt_parameters() {
+        for sigma in [1.0, 3.2, 10.0] {
+            for bits in [40, 64, 128] {
+                let params = CdtParameters::for_security(sigma, bits);
+                let target = -((bits + 1) as f64) * std::f64::consts::LN_2;
+                assert!(ln_tail_mass(sigma, params.tail_cut) <= target);
+                assert!(ln_tail_mass(sigma, params.tail_cut - 1) > target);
+                let rounding =
+                    2.0 * params.tail_cut as f64 * 2f64.powi(-(params.precision_bits as i32));
+                assert!(rounding <= 2f64.powi(-(bits as i32 + 1)));
+            }
+        }
+        // 8 sigma is about 45 bits of tail
+        let params = CdtParameters::for_security(10.0, 45);
+        assert!((params.tail_multiplier - TAIL_BOUND_MULTIPLIER).abs() < 0.5);
+    }
+}
@@ -0,0 +1,266 @@
+use rand::RngCore;
+use rand::SeedableRng;
+
+/// "expand 32-byte k"
+const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+/// Block counter reserved for deriving sub-stream keys. Output blocks count up from 0 and never
+/// reach it.
+const DERIVE_COUNTER: u64 = u64::MAX;
+
+/// Deterministic cryptographic rng running the ChaCha20 stream cipher from a 32 byte seed.
+/// https://cr.yp.to/chacha.html
+///
+/// Uses the original layout with a 64 bit block counter and a 64 bit stream id. Independent
+/// sub-streams are derived by label with `derive`, so a single seed reproduces every consumer of
+/// randomness in a run.
+#[derive(Clone, Debug)]
+pub struct ChaChaRng {
+    key: [u32; 8],
+    stream: u64,
+    counter: u64,
+    block: [u32; 16],
+    /// Next unused word of `block`. 16 when the block is exhausted.
+    index: usize,
+}
+
+impl ChaChaRng {
+    /// Rng over stream `stream` of the key `seed`.
+    pub fn new(seed: [u8; 32], stream: u64) -> Self {
+        let mut key = [0u32; 8];
+        for (word, bytes) in key.iter_mut().zip(seed.chunks_exact(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+        Self {
+            key,
+            stream,
+            counter: 0,
+            block: [0; 16],
+            index: 16,
+        }
+    }
+
+    /// The 32 byte seed of this rng.
+    pub fn seed(&self) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        for (bytes, word) in out.chunks_exact_mut(4).zip(self.key) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        out
+    }
+
+    /// Derive an independent rng for `label`. The output does not depend on how much of this rng
+    /// has been consumed.
+    ///
+    /// The label is absorbed 8 bytes at a time, each chunk replacing the key with the first half
+    /// of the ChaCha20 block keyed by the current key, with the chunk as stream id and a reserved
+    /// counter. The label length and the stream id are absorbed last so distinct labels never
+    /// share a chain.
+    pub fn derive(&self, label: &str) -> Self {
+        let mut key = self.key;
+        let bytes = label.as_bytes();
+        let chunks = bytes
+            .chunks(8)
+            .map(|chunk| {
+                let mut padded = [0u8; 8];
+                padded[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(padded)
+            })
+            .chain([bytes.len() as u64, self.stream]);
+        for chunk in chunks {
+            let block = chacha20_block(&key, DERIVE_COUNTER, chunk);
+            key.copy_from_slice(&block[..8]);
+        }
+        Self {
+            key,
+            stream: 0,
+            counter: 0,
+            block: [0; 16],
+            index: 16,
+        }
+    }
+
+    fn refill(&mut self) {
+        assert!(self.counter < DERIVE_COUNTER, "ChaChaRng: stream exhausted");
+        self.block = chacha20_block(&self.key, self.counter, self.stream);
+        self.counter += 1;
+        self.index = 0;
+    }
+}
+
+impl RngCore for ChaChaRng {
+    fn next_u32(&mut self) -> u32 {
+        if self.index == 16 {
+            self.refill();
+        }
+        self.index += 1;
+        self.block[self.index - 1]
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        let high = self.next_u32() as u64;
+        (high << 32) | low
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for chunk in dst.chunks_mut(4) {
+            let word = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&word[..chunk.len()]);
+        }
+    }
+}
+
+impl SeedableRng for ChaChaRng {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(seed, 0)
+    }
+}
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+/// One 64 byte ChaCha20 block as little endian words.
+fn chacha20_block(key: &[u32; 8], counter: u64, stream: u64) -> [u32; 16] {
+    let mut input = [0u32; 16];
+    input[..4].copy_from_slice(&CONSTANTS);
+    input[4..12].copy_from_slice(key);
+    input[12] = counter as u32;
+    input[13] = (counter >> 32) as u32;
+    input[14] = stream as u32;
+    input[15] = (stream >> 32) as u32;
+
+    let mut state = input;
+    for _ in 0..10 {
+        // column rounds
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        // diagonal rounds
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+    for (s, i) in state.iter_mut().zip(input) {
+        *s = s.wrapping_add(i);
+    }
+    state
+}
+
+#[cfg(test)]
+mod test {
+    use rand::Rng;
+
+    use super::*;
+
+    /// Key `00 01 .. 1f` used by the RFC 8439 test vectors.
+    fn rfc_key() -> [u8; 32] {
+        std::array::from_fn(|i| i as u8)
+    }
+
+    #[test]
+    fn chacha20_rfc8439_block() {
+        // RFC 8439 section 2.3.2, nonce 00:00:00:09:00:00:00:4a:00:00:00:00 and block count 1.
+        // The 32 bit counter and first nonce word form our 64 bit counter.
+        let key = ChaChaRng::new(rfc_key(), 0).key;
+        let block = chacha20_block(&key, 1 | (0x09000000 << 32), 0x4a000000);
+        let expected: [u8; 64] = [
+            0x10, 0xf1, 0xe7, 0xe4, 0xd1, 0x3b, 0x59, 0x15, 0x50, 0x0f, 0xdd, 0x1f, 0xa3, 0x20,
+            0x71, 0xc4, 0xc7, 0xd1, 0xf4, 0xc7, 0x33, 0xc0, 0x68, 0x03, 0x04, 0x22, 0xaa, 0x9a,
+            0xc3, 0xd4, 0x6c, 0x4e, 0xd2, 0x82, 0x64, 0x46, 0x07, 0x9f, 0xaa, 0x09, 0x14, 0xc2,
+            0xd7, 0x05, 0xd9, 0x8b, 0x02, 0xa2, 0xb5, 0x12, 0x9c, 0xd1, 0xde, 0x16, 0x4e, 0xb9,
+            0xcb, 0xd0, 0x83, 0xe8, 0xa2, 0x50, 0x3c, 0x4e,
+        ];
+        let bytes = block
+            .iter()
+            .flat_map(|w| w.to_le_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn chacha20_keystream() {
+        // RFC 8439 appendix A.1 test vector 1, the all zero key and nonce
+        let mut rng = ChaChaRng::from_seed([0; 32]);
+        let mut bytes = [0u8; 16];
+        rng.fill_bytes(&mut bytes);
+        assert_eq!(
+            bytes,
+            [
+                0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90, 0x40, 0x5d, 0x6a, 0xe5, 0x53, 0x86,
+                0xbd, 0x28
+            ]
+        );
+
+        // word, u64 and byte reads consume the same stream
+        let mut words = ChaChaRng::new(rfc_key(), 3);
+        let mut wide = ChaChaRng::new(rfc_key(), 3);
+        let mut bytes = ChaChaRng::new(rfc_key(), 3);
+        let mut buffer = [0u8; 200];
+        bytes.fill_bytes(&mut buffer);
+        for chunk in buffer.chunks(8) {
+            let low = words.next_u32() as u64;
+            let high = words.next_u32() as u64;
+            assert_eq!((high << 32) | low, wide.next_u64());
+            assert_eq!(chunk, &((high << 32) | low).to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    #[test]
+    fn chacha_seed_and_streams() {
+        let seed = rfc_key();
+        let mut a = ChaChaRng::new(seed, 0);
+        let mut b = ChaChaRng::new(seed, 0);
+        let mut c = ChaChaRng::new(seed, 1);
+        assert_eq!(a.seed(), seed);
+        let a_out = (0..100).map(|_| a.random::<u64>()).collect::<Vec<_>>();
+        let b_out = (0..100).map(|_| b.random::<u64>()).collect::<Vec<_>>();
+        let c_out = (0..100).map(|_| c.random::<u64>()).collect::<Vec<_>>();
+        assert_eq!(a_out, b_out);
+        assert_ne!(a_out, c_out);
+    }
+
+    #[test]
+    fn chacha_derive() {
+        let mut root = ChaChaRng::new(rfc_key(), 0);
+        let first = root.derive("sumcheck").random::<u128>();
+        // derivation ignores consumed output
+        root.random::<u128>();
+        assert_eq!(root.derive("sumcheck").random::<u128>(), first);
+
+        let labels = [
+            "",
+            "a",
+            "b",
+            "sumcheck",
+            "sumcheck\0",
+            "a longer label spanning chunks",
+        ];
+        let outputs = labels
+            .iter()
+            .map(|label| root.derive(label).random::<u128>())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(outputs.len(), labels.len());
+        // different streams of the same seed derive different children
+        assert_ne!(
+            ChaChaRng::new(rfc_key(), 1).derive("a").random::<u128>(),
+            root.derive("a").random::<u128>()
+        );
+        // derived rngs are themselves derivable
+        assert_ne!(
+            root.derive("a").derive("b").random::<u128>(),
+            root.derive("b").random::<u128>()
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::*;
+
+/// Largest sigma sampled directly from a `GaussianCDT`. Tables hold at most
+/// `2 * ceil(TAIL_BOUND_MULTIPLIER * MAX_TABLE_SIGMA) + 1` entries.
+const MAX_TABLE_SIGMA: f64 = 16.0;
+/// Smoothing parameter of the integers for epsilon = 2^-64, in units of standard deviation,
+/// `sqrt(ln(2 + 2 / eps) / pi) / sqrt(2 * pi)`.
+const SMOOTHING_PARAMETER: f64 = 1.5109;
+
+/// Discrete Gaussian sampler for large sigma, by the convolution technique of Micciancio and
+/// Walter. https://eprint.iacr.org/2017/259.pdf
+///
+/// A sample is `z * x_1 + x_2` for independent `x_1 ~ D_{sigma_1}`, `x_2 ~ D_{sigma_2}` and an
+/// integer `z`, which is statistically close to `D_{sigma}` with
+/// `sigma^2 = z^2 * sigma_1^2 + sigma_2^2` when `sigma_2 >= sqrt(2) * z * eta` and
+/// `sigma_1 >= sqrt(2) * eta` for the smoothing parameter `eta`. Both children are recursively
+/// built the same way until they are small enough for a constant time `GaussianCDT`. Choosing
+/// `z ~ sqrt(sigma)` makes both children roughly `sqrt(sigma)`, so the tree has `O(log log sigma)`
+/// depth, `O(log sigma)` tables and each table has at most a few hundred entries regardless of
+/// the target.
+#[derive(Clone)]
+pub enum ConvolutionSampler {
+    Table(Arc<GaussianCDT>),
+    Convolution {
+        sigma: f64,
+        z: i64,
+        scaled: Box<ConvolutionSampler>,
+        offset: Box<ConvolutionSampler>,
+    },
+}
+
+impl ConvolutionSampler {
+    /// Build a sampler for elements of `F` with standard deviation `sigma`. The achieved sigma is
+    /// within a relative 10^-5 of the target, see `sigma`.
+    pub fn new<F: Element>(sigma: f64) -> Self {
+        assert!(
+            sigma.is_finite() && sigma >= std::f64::consts::SQRT_2 * SMOOTHING_PARAMETER,
+            "ConvolutionSampler: sigma {sigma} is below the smoothing parameter"
+        );
+        if sigma <= MAX_TABLE_SIGMA {
+            return Self::Table(GaussianCDT::new::<F>(sigma));
+        }
+        let eta = std::f64::consts::SQRT_2 * SMOOTHING_PARAMETER;
+        // sigma is above MAX_TABLE_SIGMA so z >= 2
+        let z = (sigma / eta).sqrt().floor() as i64;
+        let offset = Self::new::<F>(eta * z as f64);
+        let scaled = Self::new::<F>((sigma.powi(2) - offset.sigma().powi(2)).sqrt() / z as f64);
+        let sigma = ((z as f64 * scaled.sigma()).powi(2) + offset.sigma().powi(2)).sqrt();
+        Self::Convolution {
+            sigma,
+            z,
+            scaled: Box::new(scaled),
+            offset: Box::new(offset),
+        }
+    }
+
+    /// Standard deviation of the distribution actually sampled.
+    pub fn sigma(&self) -> f64 {
+        match self {
+            Self::Table(cdt) => cdt.sigma,
+            Self::Convolution { sigma, .. } => *sigma,
+        }
+    }
+
+    /// Sample an integer. Every call draws from every table in the tree once, so the number of
+    /// operations does not depend on the output.
+    pub fn sample_int<R: Rng>(&self, rng: &mut R) -> i64 {
+        match self {
+            Self::Table(cdt) => cdt.sample_ct_displacement(rng),
+            Self::Convolution {
+                z, scaled, offset, ..
+            } => z * scaled.sample_int(rng) + offset.sample_int(rng),
+        }
+    }
+
+    /// Number of CDT tables in the tree and the total number of table entries.
+    pub fn table_size(&self) -> (usize, usize) {
+        match self {
+            Self::Table(cdt) => (1, cdt.fixed_point.len()),
+            Self::Convolution { scaled, offset, .. } => {
+                let (scaled_tables, scaled_entries) = scaled.table_size();
+                let (offset_tables, offset_entries) = offset.table_size();
+                (
+                    scaled_tables + offset_tables,
+                    scaled_entries + offset_entries,
+                )
+            }
+        }
+    }
+
+    /// Exact output distribution of the sampler, computed by convolving the table
+    /// probabilities. The support has about `16 * sigma` entries and computing it costs about
+    /// `sigma^1.5` operations, so this is meant for reports and tests.
+    pub fn distribution(&self) -> BTreeMap<i64, f64> {
+        match self {
+            Self::Table(cdt) => cdt
+                .displacements
+                .iter()
+                .map(|(_, disp)| (*disp as i64, cdt.prob(*disp)))
+                .collect(),
+            Self::Convolution {
+                z, scaled, offset, ..
+            } => {
+                let offset = offset.distribution();
+                let mut out = BTreeMap::new();
+                for (x_1, p_1) in scaled.distribution() {
+                    for (x_2, p_2) in &offset {
+                        *out.entry(z * x_1 + x_2).or_default() += p_1 * p_2;
+                    }
+                }
+                out
+            }
+        }
+    }
+
+    /// Statistical distance between the output distribution and the ideal discrete Gaussian
+    /// with the achieved sigma. Limited to about 10^-15 by floating point precision.
+    pub fn statistical_distance(&self) -> f64 {
+        let sigma = self.sigma();
+        let ideal = |x: i64| f64::exp(-(x as f64).powi(2) / (2.0 * sigma * sigma));
+        // mass beyond 12 sigma is below 10^-31
+        let support = (12.0 * sigma).ceil() as i64;
+        let normalizer = (-support..=support).map(ideal).sum::<f64>();
+        let distribution = self.distribution();
+        let mut distance = 0f64;
+        for x in -support..=support {
+            let p = distribution.get(&x).copied().unwrap_or_default();
+            distance += (p - ideal(x) / normalizer).abs();
+        }
+        // outputs outside the ideal support
+        distance += distribution
+            .iter()
+            .filter(|(x, _)| x.abs() > support)
+            .map(|(_, p)| p)
+            .sum::<f64>();
+        let distance = distance / 2.0;
+        log::info!(
+            "Convolution sampler sigma {sigma}: {:?} tables, statistical distance {distance:e}",
+            self.table_size()
+        );
+        distance
+    }
+}
+
+impl ShortDistribution for ConvolutionSampler {
+    fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> E {
+        let x = self.sample_int(rng);
+        assert!(
+            x.unsigned_abs() as u128 <= E::CARDINALITY / 2,
+            "ConvolutionSampler: sample {x} does not fit in the field"
+        );
+        let magnitude = E::from(x.unsigned_abs() as u128);
+        if x < 0 {
+            E::zero() - magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    fn tail_bound(&self) -> u128 {
+        match self {
+            Self::Table(cdt) => cdt.tail_bound(),
+            Self::Convolution {
+                z, scaled, offset, ..
+            } => z.unsigned_abs() as u128 * scaled.tail_bound() + offset.tail_bound(),
+        }
+    }
+
+    fn expected_norm(&self, len: usize) -> f64 {
+        (len as f64).sqrt() * self.sigma()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn convolution_parameters() {
+        for sigma in [3.0, 16.0, 20.0, 100.0, 1234.5, 1e5, 1e8] {
+            let sampler = ConvolutionSampler::new::<Field>(sigma);
+            assert!(
+                ((sampler.sigma() - sigma) / sigma).abs() < 1e-5,
+                "target {sigma}, achieved {}",
+                sampler.sigma()
+            );
+            let (tables, entries) = sampler.table_size();
+            // a direct CDT for 1e8 would have 1.6e9 entries
+            assert!(tables <= 16, "{tables} tables for sigma {sigma}");
+            assert!(entries <= tables * 257);
+            assert!(ShortDistribution::tail_bound(&sampler) as f64 >= 8.0 * sigma);
+        }
+    }
+
+    #[test]
+    fn convolution_mean_std_dev() {
+        let rng = &mut crate::test::test_rng();
+
+        for sigma in [50.0, 2000.0, 1e6] {
+            let sampler = ConvolutionSampler::new::<Field>(sigma);
+            const TOTAL_SAMPLES: usize = 100_000;
+            let samples = (0..TOTAL_SAMPLES)
+                .map(|_| sampler.sample_int(rng) as f64)
+                .collect::<Vec<_>>();
+            let mean = samples.iter().sum::<f64>() / TOTAL_SAMPLES as f64;
+            // check that mean < 4*sigma/sqrt(N)
+            assert!(mean.abs() < 4. * sigma / (TOTAL_SAMPLES as f64).sqrt());
+            let variance =
+                samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / TOTAL_SAMPLES as f64;
+            let percent_diff = ((variance.sqrt() - sigma) / sigma).abs();
+            // measured std_dev within 1% of sigma
+            assert!(
+                percent_diff < 0.01,
+                "sigma {sigma} std_dev {}",
+                variance.sqrt()
+            );
+        }
+    }
+
+    #[test]
+    fn convolution_statistical_distance() {
+        for sigma in [10.0, 40.0, 150.0, 400.0] {
+            let sampler = ConvolutionSampler::new::<Field>(sigma);
+            let distribution = sampler.distribution();
+            assert!((distribution.values().sum::<f64>() - 1.0).abs() < 1e-12);
+            let distance = sampler.statistical_distance();
+            assert!(distance < 1e-10, "sigma {sigma} distance {distance:e}");
+        }
+    }
+
+    #[test]
+    fn convolution_wide_elements() {
+        // samples beyond i32 keep their value in the field
+        let sampler = ConvolutionSampler::new::<Field>(1e9);
+        let mut ints = crate::test::test_rng();
+        let mut elements = ints.clone();
+        let mut wide = 0;
+        for _ in 0..2000 {
+            let x = sampler.sample_int(&mut ints);
+            let e: Field = ShortDistribution::sample(&sampler, &mut elements);
+            assert_eq!(e.displacement(), x as i128);
+            wide += (i32::try_from(x).is_err()) as usize;
+        }
+        assert!(wide > 0);
+    }
+}
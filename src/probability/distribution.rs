@@ -0,0 +1,250 @@
+use crate::*;
+
+/// A distribution over short field elements, used for LWE secrets and errors and SIS openings.
+///
+/// Elements are sampled by displacement, so a sample is short if its distance from zero is
+/// small. Implementations describe their output with a hard `tail_bound` on the displacement of
+/// each entry and the `expected_norm` of a sampled vector.
+pub trait ShortDistribution {
+    /// Sample a single element.
+    fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> E;
+
+    /// Largest distance from zero of any sampled entry.
+    fn tail_bound(&self) -> u128;
+
+    /// Expected Euclidean norm of a vector of `len` samples.
+    fn expected_norm(&self, len: usize) -> f64;
+
+    /// Sample a vector of `len` entries. Entries are independent unless the distribution is
+    /// defined over whole vectors.
+    fn sample_vector<E: Element, R: Rng>(&self, len: usize, rng: &mut R) -> Vector<E> {
+        (0..len).map(|_| self.sample(rng)).collect()
+    }
+}
+
+/// Uniform over the displacements `[-bound, bound]`. A bound of 1 is the uniform ternary
+/// distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniformBounded {
+    pub bound: u32,
+}
+
+impl UniformBounded {
+    pub fn new(bound: u32) -> Self {
+        Self { bound }
+    }
+}
+
+impl ShortDistribution for UniformBounded {
+    fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> E {
+        E::sample_centered(self.bound as u128, rng)
+    }
+
+    fn tail_bound(&self) -> u128 {
+        self.bound as u128
+    }
+
+    fn expected_norm(&self, len: usize) -> f64 {
+        // variance of the discrete uniform distribution on [-b, b] is b(b + 1) / 3
+        let bound = self.bound as f64;
+        (len as f64 * bound * (bound + 1.0) / 3.0).sqrt()
+    }
+}
+
+/// Ternary vectors with exactly `weight` nonzero entries, each uniformly 1 or -1.
+///
+/// The weight is a property of whole vectors, so `sample` returns a single nonzero entry and
+/// `sample_vector` chooses the support.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixedWeightTernary {
+    pub weight: usize,
+}
+
+impl FixedWeightTernary {
+    pub fn new(weight: usize) -> Self {
+        Self { weight }
+    }
+}
+
+impl ShortDistribution for FixedWeightTernary {
+    fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> E {
+        if rng.random_bool(0.5) {
+            E::one()
+        } else {
+            E::negone()
+        }
+    }
+
+    fn tail_bound(&self) -> u128 {
+        1
+    }
+
+    fn expected_norm(&self, _len: usize) -> f64 {
+        (self.weight as f64).sqrt()
+    }
+
+    fn sample_vector<E: Element, R: Rng>(&self, len: usize, rng: &mut R) -> Vector<E> {
+        assert!(
+            self.weight <= len,
+            "FixedWeightTernary: weight {} exceeds vector length {len}",
+            self.weight
+        );
+        let mut out = Vector::new(len);
+        for i in rand::seq::index::sample(rng, len, self.weight) {
+            out[i] = self.sample(rng);
+        }
+        out
+    }
+}
+
+/// Centered binomial with parameter eta, `sum_{i < eta} (a_i - b_i)` for uniform bits `a_i, b_i`.
+/// Supported on `[-eta, eta]` with variance `eta / 2`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CenteredBinomial {
+    pub eta: u32,
+}
+
+impl CenteredBinomial {
+    pub fn new(eta: u32) -> Self {
+        Self { eta }
+    }
+}
+
+impl ShortDistribution for CenteredBinomial {
+    fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> E {
+        let mut out = 0i32;
+        for _ in 0..self.eta {
+            out += rng.random_bool(0.5) as i32 - rng.random_bool(0.5) as i32;
+        }
+        E::at_displacement(out)
+    }
+
+    fn tail_bound(&self) -> u128 {
+        self.eta as u128
+    }
+
+    fn expected_norm(&self, len: usize) -> f64 {
+        (len as f64 * self.eta as f64 / 2.0).sqrt()
+    }
+}
+
+impl ShortDistribution for GaussianCDT {
+    fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> E {
+        assert_eq!(
+            self.cardinality,
+            E::CARDINALITY,
+            "GaussianCDT: sampling an element from a different field than the table was built for"
+        );
+        self.sample_ct(rng)
+    }
+
+    fn tail_bound(&self) -> u128 {
+        GaussianCDT::tail_bound(self)
+    }
+
+    fn expected_norm(&self, len: usize) -> f64 {
+        (len as f64).sqrt() * self.sigma
+    }
+}
+
+impl<E: Element> Vector<E> {
+    /// Sample a vector of `len` entries from a short distribution.
+    pub fn sample_from<D: ShortDistribution, R: Rng>(len: usize, dist: &D, rng: &mut R) -> Self {
+        dist.sample_vector(len, rng)
+    }
+}
+
+impl<E: Element> Matrix<E> {
+    /// Sample a matrix from a short distribution, one row at a time.
+    pub fn sample_from<D: ShortDistribution, R: Rng>(
+        height: usize,
+        width: usize,
+        dist: &D,
+        rng: &mut R,
+    ) -> Self {
+        Self::from_rows(
+            (0..height)
+                .map(|_| dist.sample_vector(width, rng))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    /// Sample `len` entries and compare the measured norm with `expected_norm`.
+    fn check_distribution<D: ShortDistribution>(dist: &D) {
+        let rng = &mut crate::test::test_rng();
+        const LEN: usize = 10_000;
+        let v = Vector::<Field>::sample_from(LEN, dist, rng);
+        assert_eq!(v.len(), LEN);
+        assert!(v.norm_inf() <= dist.tail_bound());
+        let expected = dist.expected_norm(LEN);
+        let measured = v.norm_l2();
+        assert!(
+            ((measured - expected) / expected).abs() < 0.05,
+            "measured norm {measured}, expected {expected}"
+        );
+    }
+
+    #[test]
+    fn uniform_bounded() {
+        for bound in [1, 2, 5, 100] {
+            check_distribution(&UniformBounded::new(bound));
+        }
+        let rng = &mut crate::test::test_rng();
+        let v = Vector::<Field>::sample_from(1000, &UniformBounded::new(1), rng);
+        for disp in [-1, 0, 1] {
+            assert!(v.iter().any(|x| x.displacement() == disp));
+        }
+    }
+
+    #[test]
+    fn fixed_weight_ternary() {
+        let rng = &mut crate::test::test_rng();
+        for weight in [0, 1, 64, 256] {
+            let dist = FixedWeightTernary::new(weight);
+            let v = Vector::<Field>::sample_from(256, &dist, rng);
+            assert_eq!(v.norm_inf(), weight.min(1) as u128);
+            assert_eq!(v.norm_l1(), weight as u128);
+            assert_eq!(v.norm_l2(), dist.expected_norm(256));
+        }
+        check_distribution(&FixedWeightTernary::new(10_000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixed_weight_too_heavy() {
+        let rng = &mut crate::test::test_rng();
+        Vector::<Field>::sample_from(4, &FixedWeightTernary::new(5), rng);
+    }
+
+    #[test]
+    fn centered_binomial() {
+        for eta in [1, 2, 3, 8] {
+            check_distribution(&CenteredBinomial::new(eta));
+        }
+    }
+
+    #[test]
+    fn gaussian_cdt() {
+        for sigma in [1.0, 3.2, 10.0] {
+            check_distribution(GaussianCDT::new::<Field>(sigma).as_ref());
+        }
+    }
+
+    #[test]
+    fn matrix_sample_from() {
+        let rng = &mut crate::test::test_rng();
+        let m = Matrix::<Field>::sample_from(16, 32, &FixedWeightTernary::new(4), rng);
+        assert_eq!(m.dimension(), (16, 32));
+        for i in 0..16 {
+            let row: Vector<Field> = m[i].to_vec().into();
+            assert_eq!(row.norm_l1(), 4);
+        }
+    }
+}
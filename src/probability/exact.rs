@@ -0,0 +1,190 @@
+use crate::*;
+
+/// Discrete Gaussian sampler using only integer arithmetic, by Canonne, Kamath and Steinke.
+/// https://arxiv.org/abs/2004.00010
+///
+/// The variance is the rational `sigma_sq_num / sigma_sq_den`. Samples are drawn from a discrete
+/// Laplace distribution and accepted with probability `exp(-(|y| - sigma^2 / t)^2 / (2 sigma^2))`,
+/// with every `exp(-gamma)` coin flipped exactly as a sequence of Bernoulli trials with rational
+/// bias. No floating point is used, so the output is exactly `D_{Z, sigma}` and bit for bit
+/// reproducible from a seeded rng on any platform.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExactGaussian {
+    pub sigma_sq_num: u32,
+    pub sigma_sq_den: u32,
+}
+
+impl ExactGaussian {
+    pub fn new(sigma_sq_num: u32, sigma_sq_den: u32) -> Self {
+        assert!(
+            sigma_sq_num > 0 && sigma_sq_den > 0,
+            "ExactGaussian: variance must be positive"
+        );
+        Self {
+            sigma_sq_num,
+            sigma_sq_den,
+        }
+    }
+
+    /// Sampler with integer standard deviation `sigma`.
+    pub fn with_sigma(sigma: u16) -> Self {
+        Self::new(sigma as u32 * sigma as u32, 1)
+    }
+
+    /// Sample an integer from `D_{Z, sigma}` with no tail cut.
+    pub fn sample_int<R: Rng>(&self, rng: &mut R) -> i64 {
+        let num = self.sigma_sq_num as u128;
+        let den = self.sigma_sq_den as u128;
+        // t = floor(sigma) + 1
+        let t = (num / den).isqrt() + 1;
+        loop {
+            let y = Self::sample_laplace(t, rng);
+            // gamma = (|y| - sigma^2 / t)^2 / (2 sigma^2)
+            //       = (|y| * den * t - num)^2 / (2 * num * den * t^2)
+            let diff = (y.unsigned_abs() as u128 * den * t).abs_diff(num);
+            if Self::bernoulli_exp(diff * diff, 2 * num * den * t * t, rng) {
+                return y;
+            }
+        }
+    }
+
+    /// Discrete Laplace with scale `t`, `P(y) ~ exp(-|y| / t)`.
+    fn sample_laplace<R: Rng>(t: u128, rng: &mut R) -> i64 {
+        loop {
+            let u = rng.random_range(0..t);
+            if !Self::bernoulli_exp(u, t, rng) {
+                continue;
+            }
+            let mut v = 0;
+            while Self::bernoulli_exp(1, 1, rng) {
+                v += 1;
+            }
+            let x = (u + t * v) as i64;
+            let negative = rng.random_bool(0.5);
+            if negative && x == 0 {
+                continue;
+            }
+            return if negative { -x } else { x };
+        }
+    }
+
+    /// Flip a coin with probability `exp(-num / den)` of returning true.
+    fn bernoulli_exp<R: Rng>(num: u128, den: u128, rng: &mut R) -> bool {
+        for _ in 0..num / den {
+            if !Self::bernoulli_exp_small(1, 1, rng) {
+                return false;
+            }
+        }
+        Self::bernoulli_exp_small(num % den, den, rng)
+    }
+
+    /// `bernoulli_exp` for `num <= den`. Returns true when the first failure of the trials
+    /// `Bernoulli(gamma / k)`, `k = 1, 2, ...`, happens at an odd `k`.
+    fn bernoulli_exp_small<R: Rng>(num: u128, den: u128, rng: &mut R) -> bool {
+        let mut k = 1;
+        while rng.random_range(0..den * k) < num {
+            k += 1;
+        }
+        k % 2 == 1
+    }
+}
+
+impl ShortDistribution for ExactGaussian {
+    /// Samples beyond `tail_bound` are rejected, giving the exact Gaussian conditioned on the
+    /// tail cut.
+    fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> E {
+        let tail_bound = self.tail_bound();
+        loop {
+            let x = self.sample_int(rng);
+            if x.unsigned_abs() as u128 <= tail_bound {
+                return E::at_displacement(x as i32);
+            }
+        }
+    }
+
+    /// `ceil(TAIL_BOUND_MULTIPLIER * sigma)`, matching `GaussianCDT`.
+    fn tail_bound(&self) -> u128 {
+        let multiplier = TAIL_BOUND_MULTIPLIER as u128;
+        let num = multiplier * multiplier * self.sigma_sq_num as u128;
+        let den = self.sigma_sq_den as u128;
+        // ceil(sqrt(num / den))
+        let floor = (num / den).isqrt();
+        if floor * floor * den == num {
+            floor
+        } else {
+            floor + 1
+        }
+    }
+
+    fn expected_norm(&self, len: usize) -> f64 {
+        (len as f64 * self.sigma_sq_num as f64 / self.sigma_sq_den as f64).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn exact_reproducible() {
+        let sampler = ExactGaussian::new(32, 3);
+        let run = |seed| {
+            let rng = &mut ChaChaRng::seed_from_u64(seed);
+            (0..1000)
+                .map(|_| sampler.sample_int(rng))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn exact_bernoulli_exp() {
+        let rng = &mut crate::test::test_rng();
+        const TOTAL_SAMPLES: usize = 100_000;
+        for (num, den) in [(0, 1), (1, 3), (1, 1), (5, 2), (7, 1)] {
+            let count = (0..TOTAL_SAMPLES)
+                .filter(|_| ExactGaussian::bernoulli_exp(num, den, rng))
+                .count() as f64;
+            let p = f64::exp(-(num as f64) / den as f64);
+            let expected = p * TOTAL_SAMPLES as f64;
+            // within 5 standard deviations of the binomial count
+            assert!(
+                (count - expected).abs() <= 5.0 * (expected * (1.0 - p)).sqrt(),
+                "exp(-{num}/{den}): {count} of {TOTAL_SAMPLES}"
+            );
+        }
+    }
+
+    #[test]
+    fn exact_chi_squared_fit() -> Result<()> {
+        type Field = OxfoiScalar;
+        let rng = &mut crate::test::test_rng();
+
+        for (num, den) in [(1, 1), (9, 4), (10, 1), (361, 16), (900, 1)] {
+            let sampler = ExactGaussian::new(num, den);
+            let sigma_sq = num as f64 / den as f64;
+            let tail = sampler.tail_bound() as i64;
+            test_sampler(
+                100_000,
+                0.001,
+                -tail..=tail,
+                |x| f64::exp(-(x as f64).powi(2) / (2.0 * sigma_sq)),
+                || sampler.sample::<Field, _>(rng).displacement() as i64,
+            )?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn exact_tail_bound() {
+        assert_eq!(ExactGaussian::with_sigma(3).tail_bound(), 24);
+        assert_eq!(ExactGaussian::new(10, 1).tail_bound(), 26);
+        assert_eq!(
+            ExactGaussian::new(10, 1).tail_bound(),
+            GaussianCDT::tail_cut(10f64.sqrt())
+        );
+    }
+}
@@ -10,7 +10,7 @@ use crate::*;
 static CDT_CACHE: LazyLock<RwLock<HashMap<(u128, u32), Arc<GaussianCDT>>>> =
     LazyLock::new(|| RwLock::new(HashMap::default()));
 /// How far from the standard deviation we should sample.
-const TAIL_BOUND_MULTIPLIER: f64 = 8.0;
+pub(crate) const TAIL_BOUND_MULTIPLIER: f64 = 8.0;
 
 /// An instance of a cumulative distribution table for a finite field, with a specificsigma
 /// and constant tail bounds of TAIL_BOUND_MULTIPLIER * σ.
@@ -30,6 +30,10 @@ pub struct GaussianCDT {
     pub displacements: Vec<(f64, i32)>,
     // sum of the PDF evaluated over all possible output values
     pub normalized_sum: f64,
+    /// Fixed point cumulative probabilities scaled by 2^64. Entry `i` is the probability of
+    /// sampling one of the first `i + 1` displacements, so the table is one shorter than
+    /// `displacements`.
+    pub fixed_point: Vec<u64>,
 }
 
 impl GaussianCDT {
@@ -46,6 +50,57 @@ impl GaussianCDT {
         panic!("sampled probability is outside CDT");
     }
 
+    /// Sample an element in time independent of the output.
+    ///
+    /// Draws a uniform `u64` and compares it against every entry of the fixed point table without
+    /// branching or indexing on secret data. Displacements with probability below 2^-64 are
+    /// never sampled.
+    ///
+    /// The table is the f64 CDF scaled by 2^64, so each entry is only accurate to about 2^-53
+    /// and the output is roughly 2^-45 from `D_{Z, sigma}` in statistical distance, see
+    /// `GaussianCDT::precision`. The extra bits of the fixed point representation add no
+    /// accuracy.
+    pub fn sample_ct<F: Element, R: Rng>(&self, rng: &mut R) -> F {
+        let index = self.fixed_point_index(rng.random::<u64>());
+        // the table covers the contiguous displacements [-dist, dist]
+        let dist = self.displacements[0].1.unsigned_abs() as u128;
+        F::from(index as u128) - F::from(dist)
+    }
+
+    /// Constant time sample of the displacement itself, for samplers that combine several draws
+    /// before reducing into the field.
+    pub fn sample_ct_displacement<R: Rng>(&self, rng: &mut R) -> i64 {
+        let index = self.fixed_point_index(rng.random::<u64>());
+        index as i64 + self.displacements[0].1 as i64
+    }
+
+    /// Number of entries of the fixed point table that are `<= r`, which is the index of the
+    /// sampled displacement.
+    fn fixed_point_index(&self, r: u64) -> usize {
+        let mut index = 0u64;
+        for cumulative in &self.fixed_point {
+            // (2^64 + r - cumulative) has bit 64 set exactly when r >= cumulative
+            index += (((1u128 << 64) + r as u128 - *cumulative as u128) >> 64) as u64;
+        }
+        index as usize
+    }
+
+    /// Largest absolute displacement in the table. Samples never exceed this distance from
+    /// zero.
+    pub fn tail_bound(&self) -> u128 {
+        self.displacements
+            .iter()
+            .map(|(_, disp)| disp.unsigned_abs() as u128)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Largest displacement in a table for `sigma`, `ceil(TAIL_BOUND_MULTIPLIER * sigma)`. Matches
+    /// `tail_bound` without building the table.
+    pub fn tail_cut(sigma: f64) -> u128 {
+        (TAIL_BOUND_MULTIPLIER * sigma).ceil() as u128
+    }
+
     /// Probability of selecting a certain displacement in this CDT.
     pub(crate) fn prob(&self, disp: i32) -> f64 {
         for i in 1..self.displacements.len() {
@@ -94,11 +149,16 @@ impl GaussianCDT {
             normalized_sum += *prob;
             *prob = prob_floor;
         }
+        let fixed_point = displacements[1..]
+            .iter()
+            .map(|(prob_floor, _)| (prob_floor * 2f64.powi(64)) as u64)
+            .collect();
         let out = Arc::new(Self {
             cardinality: F::CARDINALITY,
             sigma,
             displacements,
             normalized_sum: total_prob,
+            fixed_point,
         });
         CDT_CACHE
             .write()
@@ -111,116 +171,142 @@ impl GaussianCDT {
 #[cfg(test)]
 mod test {
 
-    use crate::probability::chi_sq::chi_sq_95;
-
     use super::*;
 
-    #[test]
-    fn cdt_mean() {
+    /// Sample 100,000 values for each sigma in `[1, 10)` and check a statistic that is standard
+    /// normal if the sampler matches the table, splitting the significance across all sigmas.
+    fn check_cdt_statistic(statistic: impl Fn(&GaussianCDT, &Histogram) -> f64) {
         type Field = OxfoiScalar;
-        let rng = &mut rand::rng();
+        let rng = &mut crate::test::test_rng();
 
-        for i in 10..100 {
+        let sigmas = (10..100).collect::<Vec<_>>();
+        // the square of a standard normal is chi-squared with 1 degree of freedom
+        let critical = chi_sq_quantile(1.0 - 0.001 / sigmas.len() as f64, 1).sqrt();
+        for i in sigmas {
             let sigma = (i as f64) / 10.;
             let cdt = GaussianCDT::new::<Field>(sigma);
-            let mut samples = HashMap::<i32, usize>::default();
-            let mut sum = 0f64;
-            const TOTAL_SAMPLES: usize = 100_000;
-            for _ in 0..TOTAL_SAMPLES {
-                let disp = cdt.sample::<Field, _>(rng).displacement();
-                sum += disp as f64;
-                *samples.entry(disp as i32).or_default() += 1;
-            }
-            // check that mean < 3*sigma/sqrt(N)
+            let histogram = (0..100_000)
+                .map(|_| cdt.sample::<Field, _>(rng).displacement() as i64)
+                .collect::<Histogram>();
+            let z = statistic(&cdt, &histogram);
             assert!(
-                (sum / TOTAL_SAMPLES as f64).abs() < (3. * sigma) / (TOTAL_SAMPLES as f64).sqrt()
+                z.abs() < critical,
+                "sigma {sigma}: statistic {z} beyond {critical}"
             );
         }
     }
 
+    /// `E[x^k]` under the table probabilities.
+    fn cdt_moment(cdt: &GaussianCDT, k: i32) -> f64 {
+        let tail = cdt.tail_bound() as i32;
+        (-tail..=tail)
+            .map(|x| cdt.prob(x) * (x as f64).powi(k))
+            .sum()
+    }
+
+    #[test]
+    fn cdt_mean() {
+        // the table is symmetric, so the sample mean is 0 with variance E[x^2] / N
+        check_cdt_statistic(|cdt, histogram| {
+            histogram.mean() * (histogram.total() as f64 / cdt_moment(cdt, 2)).sqrt()
+        });
+    }
+
     #[test]
     fn cdt_std_dev() {
         type Field = OxfoiScalar;
-        let rng = &mut rand::rng();
 
+        // the table standard deviation is within 1% of sigma
         for i in 10..100 {
             let sigma = (i as f64) / 10.;
             let cdt = GaussianCDT::new::<Field>(sigma);
-            let mut samples = HashMap::<i32, usize>::default();
-            let mut sum = 0f64;
-            const TOTAL_SAMPLES: usize = 100_000;
-            for _ in 0..TOTAL_SAMPLES {
-                let disp = cdt.sample::<Field, _>(rng).displacement();
-                sum += disp as f64;
-                *samples.entry(disp as i32).or_default() += 1;
-            }
-            let mean = sum / TOTAL_SAMPLES as f64;
-            let mut variance = 0f64;
-            for (disp, count) in samples {
-                variance += count as f64 * (disp as f64 - mean).powi(2);
-            }
-            let variance = variance / TOTAL_SAMPLES as f64;
-            let std_dev = variance.sqrt();
-            let percent_diff = ((std_dev - sigma) / sigma).abs();
-            // measured std_dev within 1% ofsigma
-            assert!(percent_diff < 0.01);
+            let std_dev = cdt_moment(&cdt, 2).sqrt();
+            assert!(((std_dev - sigma) / sigma).abs() < 0.01);
         }
+
+        // and the sample variance matches it, with variance (E[x^4] - E[x^2]^2) / N
+        check_cdt_statistic(|cdt, histogram| {
+            let (m_2, m_4) = (cdt_moment(cdt, 2), cdt_moment(cdt, 4));
+            (histogram.variance() - m_2) * (histogram.total() as f64 / (m_4 - m_2 * m_2)).sqrt()
+        });
     }
 
     #[test]
     fn cdt_symmetry() {
+        // the number of positive samples among the nonzero ones is binomial with p = 1/2
+        check_cdt_statistic(|_, histogram| {
+            let (neg, pos) =
+                histogram
+                    .iter()
+                    .fold((0, 0), |(neg, pos), (x, count)| match x.signum() {
+                        -1 => (neg + count, pos),
+                        1 => (neg, pos + count),
+                        _ => (neg, pos),
+                    });
+            (pos as f64 - neg as f64) / ((pos + neg) as f64).sqrt()
+        });
+    }
+
+    /// Test a table sampler against the table probabilities for sigma in `[1, 10)`, splitting
+    /// the significance across all sigmas.
+    fn check_cdt_fit(step: usize, sample: impl Fn(&GaussianCDT, &mut ChaChaRng) -> i128) {
         type Field = OxfoiScalar;
-        let rng = &mut rand::rng();
+        let rng = &mut crate::test::test_rng();
 
-        for i in 10..100 {
+        let sigmas = (10..100).step_by(step).collect::<Vec<_>>();
+        let significance = 0.001 / sigmas.len() as f64;
+        for i in sigmas {
             let sigma = (i as f64) / 10.;
             let cdt = GaussianCDT::new::<Field>(sigma);
-            let mut total_neg = 0f64;
-            let mut total_pos = 0f64;
-            const TOTAL_SAMPLES: usize = 100_000;
-            for _ in 0..TOTAL_SAMPLES {
-                let disp = cdt.sample::<Field, _>(rng).displacement();
-                if disp < 0 {
-                    total_neg += 1.0;
-                } else if disp > 0 {
-                    total_pos += 1.0;
-                }
-            }
-            // negative and positive counts within 3% diff
-            assert!((1.0 - total_neg / total_pos).abs() < 0.03);
+            let tail = cdt.tail_bound() as i64;
+            test_sampler(
+                100_000,
+                significance,
+                -tail..=tail,
+                |x| cdt.prob(x as i32),
+                || sample(&cdt, rng) as i64,
+            )
+            .unwrap_or_else(|e| panic!("sigma {sigma}: {e}"));
         }
     }
 
     #[test]
     fn cdt_chi_squared_fit() {
+        check_cdt_fit(1, |cdt, rng| {
+            cdt.sample::<OxfoiScalar, _>(rng).displacement()
+        });
+    }
+
+    #[test]
+    fn cdt_ct_chi_squared_fit() {
+        check_cdt_fit(5, |cdt, rng| {
+            cdt.sample_ct::<OxfoiScalar, _>(rng).displacement()
+        });
+    }
+
+    #[test]
+    fn cdt_ct_index() {
         type Field = OxfoiScalar;
-        let rng = &mut rand::rng();
+        let rng = &mut crate::test::test_rng();
 
-        for i in 10..100 {
-            let sigma = (i as f64) / 10.;
+        for sigma in [1.0, 3.2, 10.0] {
             let cdt = GaussianCDT::new::<Field>(sigma);
-            let mut samples = HashMap::<i32, usize>::default();
-            const TOTAL_SAMPLES: usize = 100_000;
-            for _ in 0..TOTAL_SAMPLES {
-                let disp = cdt.sample::<Field, _>(rng).displacement();
-                *samples.entry(disp as i32).or_default() += 1;
-            }
-            let mut chi_sq = 0f64;
-            for disp in ((-sigma * 10.) as i32)..((sigma * 10.) as i32) {
-                let count = samples.entry(disp).or_default();
-                let expected = cdt.prob(disp) * TOTAL_SAMPLES as f64;
-                if expected < 1.0 {
-                    continue;
-                }
-                chi_sq += (*count as f64 - expected).powi(2) / expected;
+            assert_eq!(cdt.fixed_point.len(), cdt.displacements.len() - 1);
+            assert!(cdt.fixed_point.is_sorted());
+            let dist = cdt.tail_bound() as i32;
+            let mut draws = vec![0, u64::MAX, 1 << 63];
+            draws.extend((0..100).map(|_| rng.random::<u64>()));
+            for r in draws {
+                let index = cdt.fixed_point_index(r);
+                assert!(index < cdt.displacements.len());
+                // agrees with a direct search of the table
+                let expected = cdt.fixed_point.iter().filter(|c| **c <= r).count();
+                assert_eq!(index, expected);
             }
-            let df = samples.len() - 1;
-            let expected = chi_sq_95(df);
-            println!("{} {}", expected, chi_sq);
-            assert!(
-                chi_sq < expected,
-                "{chi_sq} outside of bound 95% {expected}"
-            );
+            // the smallest and largest draws land in the tails
+            assert_eq!(cdt.fixed_point_index(0) as i32 - dist, -dist);
+            assert!(cdt.fixed_point_index(u64::MAX) as i32 - dist > 0);
+            assert_eq!(cdt.fixed_point_index(1 << 63) as i32 - dist, 0);
         }
     }
 }
@@ -1,5 +1,21 @@
+mod analysis;
+mod chacha;
+#[cfg(test)]
 mod chi_sq;
+mod convolution;
+mod distribution;
+mod exact;
 mod gaussian;
+mod sample_z;
+mod stats;
+mod uniform;
 
-use chi_sq::*;
-use gaussian::*;
+pub use analysis::*;
+pub use chacha::*;
+pub use convolution::*;
+pub use distribution::*;
+pub use exact::*;
+pub use gaussian::*;
+pub use sample_z::*;
+pub use stats::*;
+pub use uniform::*;
@@ -0,0 +1,158 @@
+use crate::*;
+
+/// Discrete Gaussian over the integers with width `sigma` and an arbitrary real `center`, the
+/// `SampleZ` subroutine of GPV and Klein style samplers.
+///
+/// Samples by rejection: draw an integer uniformly from `[c - t * sigma, c + t * sigma]` and accept
+/// it with probability `exp(-(x - c)^2 / (2 * sigma^2))`, where `t` is `TAIL_BOUND_MULTIPLIER`.
+/// The expected number of draws is about `2 * t / sqrt(2 * pi)`, roughly 6.4, independent of sigma.
+/// Unlike `GaussianCDT` no table is built, so the center can change on every call.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SampleZ {
+    pub sigma: f64,
+    pub center: f64,
+}
+
+impl SampleZ {
+    pub fn new(sigma: f64, center: f64) -> Self {
+        assert!(
+            sigma.is_finite() && sigma > 0.0,
+            "SampleZ: sigma must be positive"
+        );
+        assert!(center.is_finite(), "SampleZ: center must be finite");
+        Self { sigma, center }
+    }
+
+    /// Sample an integer from the distribution.
+    pub fn sample_int<R: Rng>(&self, rng: &mut R) -> i64 {
+        let tail = TAIL_BOUND_MULTIPLIER * self.sigma;
+        let min = (self.center - tail).ceil() as i64;
+        let max = (self.center + tail).floor() as i64;
+        loop {
+            let x = rng.random_range(min..=max);
+            let dist = x as f64 - self.center;
+            let accept = f64::exp(-(dist * dist) / (2.0 * self.sigma * self.sigma));
+            if rng.random_bool(accept) {
+                return x;
+            }
+        }
+    }
+}
+
+impl ShortDistribution for SampleZ {
+    fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> E {
+        let x = self.sample_int(rng);
+        assert!(
+            x.unsigned_abs() as u128 <= E::CARDINALITY / 2,
+            "SampleZ: sample {x} does not fit in the field"
+        );
+        let magnitude = E::from(x.unsigned_abs() as u128);
+        if x < 0 {
+            E::zero() - magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    fn tail_bound(&self) -> u128 {
+        (self.center.abs() + TAIL_BOUND_MULTIPLIER * self.sigma).floor() as u128
+    }
+
+    fn expected_norm(&self, len: usize) -> f64 {
+        // E[x^2] = sigma^2 + c^2 up to a negligible error for sigma above the smoothing parameter
+        (len as f64 * (self.sigma * self.sigma + self.center * self.center)).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CENTERS: [f64; 4] = [0.5, -0.25, 3.7, -1000.333];
+    const TOTAL_SAMPLES: usize = 100_000;
+
+    #[test]
+    fn sample_z_mean() {
+        let rng = &mut crate::test::test_rng();
+
+        for i in (10..100).step_by(20) {
+            let sigma = (i as f64) / 10.;
+            for center in CENTERS {
+                let sampler = SampleZ::new(sigma, center);
+                let mut sum = 0f64;
+                for _ in 0..TOTAL_SAMPLES {
+                    sum += sampler.sample_int(rng) as f64;
+                }
+                let mean = sum / TOTAL_SAMPLES as f64;
+                // check that |mean - c| < 4*sigma/sqrt(N)
+                assert!(
+                    (mean - center).abs() < (4. * sigma) / (TOTAL_SAMPLES as f64).sqrt(),
+                    "sigma {sigma} center {center} mean {mean}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sample_z_std_dev() {
+        let rng = &mut crate::test::test_rng();
+
+        for i in (10..100).step_by(20) {
+            let sigma = (i as f64) / 10.;
+            for center in CENTERS {
+                let sampler = SampleZ::new(sigma, center);
+                let samples = (0..TOTAL_SAMPLES)
+                    .map(|_| sampler.sample_int(rng) as f64)
+                    .collect::<Vec<_>>();
+                let mean = samples.iter().sum::<f64>() / TOTAL_SAMPLES as f64;
+                let variance =
+                    samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / TOTAL_SAMPLES as f64;
+                let percent_diff = ((variance.sqrt() - sigma) / sigma).abs();
+                // measured std_dev within 1.5% of sigma
+                assert!(
+                    percent_diff < 0.015,
+                    "sigma {sigma} center {center} std_dev {}",
+                    variance.sqrt()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sample_z_matches_cdt_at_zero() {
+        type Field = OxfoiScalar;
+        let rng = &mut crate::test::test_rng();
+
+        let sigma = 2.5;
+        let cdt = GaussianCDT::new::<Field>(sigma);
+        let sampler = SampleZ::new(sigma, 0.0);
+        assert_eq!(ShortDistribution::tail_bound(&sampler), cdt.tail_bound());
+        let mut counts = std::collections::HashMap::<i32, usize>::default();
+        for _ in 0..TOTAL_SAMPLES {
+            let disp = sampler.sample::<Field, _>(rng).displacement();
+            *counts.entry(disp as i32).or_default() += 1;
+        }
+        for disp in -3..=3 {
+            let expected = cdt.prob(disp) * TOTAL_SAMPLES as f64;
+            let count = counts.get(&disp).copied().unwrap_or_default() as f64;
+            // within 5 standard deviations of the binomial count
+            assert!((count - expected).abs() < 5.0 * expected.sqrt());
+        }
+    }
+
+    #[test]
+    fn sample_z_wide_elements() {
+        // samples beyond i32 keep their value in the field
+        let sampler = SampleZ::new(1e10, -3e9);
+        let mut ints = crate::test::test_rng();
+        let mut elements = ints.clone();
+        let mut wide = 0;
+        for _ in 0..1000 {
+            let x = sampler.sample_int(&mut ints);
+            let e: OxfoiScalar = ShortDistribution::sample(&sampler, &mut elements);
+            assert_eq!(e.displacement(), x as i128);
+            wide += (i32::try_from(x).is_err()) as usize;
+        }
+        assert!(wide > 0);
+    }
+}
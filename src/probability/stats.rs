@@ -0,0 +1,434 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use anyhow::Result;
+
+/// Minimum expected count of a chi-squared bin. Bins below this are pooled together.
+const MIN_EXPECTED_COUNT: f64 = 5.0;
+
+/// Natural log of the gamma function for `x > 0`, by the Lanczos approximation (g = 7, n = 9).
+/// Accurate to about 15 significant digits.
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    assert!(x > 0.0, "ln_gamma is only defined for positive arguments");
+    if x < 0.5 {
+        // reflection formula
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let mut sum = COEFFICIENTS[0];
+    for (i, c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized lower incomplete gamma function `P(a, x) = gamma(a, x) / Gamma(a)`.
+///
+/// Uses the series expansion for `x < a + 1` and a continued fraction for the upper function
+/// otherwise.
+pub fn regularized_gamma_p(a: f64, x: f64) -> f64 {
+    assert!(a > 0.0, "regularized_gamma_p requires a > 0");
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let prefactor = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..1000 {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * 1e-16 {
+                break;
+            }
+        }
+        sum * prefactor
+    } else {
+        // modified Lentz's method for the continued fraction of Q(a, x)
+        const TINY: f64 = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-16 {
+                break;
+            }
+        }
+        1.0 - prefactor * h
+    }
+}
+
+/// Cumulative distribution function of the chi-squared distribution with `df` degrees of freedom.
+pub fn chi_sq_cdf(x: f64, df: usize) -> f64 {
+    assert!(df > 0, "chi-squared distribution requires df > 0");
+    regularized_gamma_p(df as f64 / 2.0, x / 2.0)
+}
+
+/// Value `x` such that `chi_sq_cdf(x, df) = p`. The critical value of a test at significance
+/// `alpha` is `chi_sq_quantile(1 - alpha, df)`.
+pub fn chi_sq_quantile(p: f64, df: usize) -> f64 {
+    assert!(
+        (0.0..1.0).contains(&p),
+        "chi_sq_quantile: probability {p} outside [0, 1)"
+    );
+    let mut high = df as f64 + 1.0;
+    while chi_sq_cdf(high, df) < p {
+        high *= 2.0;
+    }
+    let mut low = 0.0;
+    // bisection to full double precision
+    for _ in 0..200 {
+        let mid = (low + high) / 2.0;
+        if chi_sq_cdf(mid, df) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+        if high - low <= high * f64::EPSILON {
+            break;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// Probability that the Kolmogorov distribution exceeds `lambda`,
+/// `2 * sum_{k >= 1} (-1)^(k - 1) exp(-2 k^2 lambda^2)`.
+pub fn kolmogorov_p_value(lambda: f64) -> f64 {
+    if lambda < 0.2 {
+        return 1.0;
+    }
+    let mut sum = 0f64;
+    for k in 1..=100 {
+        let k = k as f64;
+        let term = (-2.0 * k * k * lambda * lambda).exp();
+        sum += if k as u64 % 2 == 1 { term } else { -term };
+        if term < 1e-16 {
+            break;
+        }
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Counts of integer valued samples.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Histogram {
+    counts: BTreeMap<i64, usize>,
+    total: usize,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a single sample.
+    pub fn add(&mut self, x: i64) {
+        *self.counts.entry(x).or_default() += 1;
+        self.total += 1;
+    }
+
+    /// Number of times `x` was recorded.
+    pub fn count(&self, x: i64) -> usize {
+        self.counts.get(&x).copied().unwrap_or_default()
+    }
+
+    /// Fraction of samples equal to `x`.
+    pub fn frequency(&self, x: i64) -> f64 {
+        self.count(x) as f64 / self.total as f64
+    }
+
+    /// Total number of samples.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Observed values and their counts in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = (i64, usize)> {
+        self.counts.iter().map(|(x, count)| (*x, *count))
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.iter().map(|(x, c)| x as f64 * c as f64).sum::<f64>() / self.total as f64
+    }
+
+    /// Population variance of the samples.
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+        self.iter()
+            .map(|(x, c)| c as f64 * (x as f64 - mean).powi(2))
+            .sum::<f64>()
+            / self.total as f64
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+impl FromIterator<i64> for Histogram {
+    fn from_iter<T: IntoIterator<Item = i64>>(iter: T) -> Self {
+        let mut out = Self::new();
+        for x in iter {
+            out.add(x);
+        }
+        out
+    }
+}
+
+/// Result of testing a histogram against an expected distribution with both a chi-squared test
+/// and a Kolmogorov-Smirnov test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GoodnessOfFit {
+    pub chi_sq: f64,
+    pub df: usize,
+    /// Probability of a chi-squared statistic at least this large if the samples follow the
+    /// expected distribution.
+    pub chi_sq_p_value: f64,
+    /// Largest difference between the empirical and expected cumulative distributions.
+    pub ks: f64,
+    /// Asymptotic Kolmogorov-Smirnov p-value. Conservative for discrete distributions.
+    pub ks_p_value: f64,
+}
+
+impl GoodnessOfFit {
+    /// Compare a histogram with the probability mass function `pmf` over `support`. The pmf is
+    /// normalized over the support, and samples outside it count against the fit.
+    ///
+    /// Values with an expected count below 5 are pooled into a single chi-squared bin. Fails on
+    /// an empty histogram.
+    pub fn new(
+        histogram: &Histogram,
+        support: RangeInclusive<i64>,
+        pmf: impl Fn(i64) -> f64,
+    ) -> Result<Self> {
+        if histogram.total() == 0 {
+            anyhow::bail!("Goodness of fit test needs at least one sample");
+        }
+        let n = histogram.total() as f64;
+        let normalizer = support.clone().map(&pmf).sum::<f64>();
+
+        let mut chi_sq = 0f64;
+        let mut bins = 0usize;
+        let mut pooled_expected = 0f64;
+        let mut pooled_count = 0usize;
+        let mut in_support = 0usize;
+        let mut ks = 0f64;
+        // samples below the support are part of the empirical cdf
+        let mut empirical_cdf = histogram
+            .iter()
+            .take_while(|(x, _)| x < support.start())
+            .map(|(_, count)| count)
+            .sum::<usize>() as f64
+            / n;
+        let mut expected_cdf = 0f64;
+        for x in support.clone() {
+            let p = pmf(x) / normalizer;
+            let count = histogram.count(x);
+            in_support += count;
+            let expected = p * n;
+            if expected < MIN_EXPECTED_COUNT {
+                pooled_expected += expected;
+                pooled_count += count;
+            } else {
+                chi_sq += (count as f64 - expected).powi(2) / expected;
+                bins += 1;
+            }
+            empirical_cdf += count as f64 / n;
+            expected_cdf += p;
+            ks = ks.max((empirical_cdf - expected_cdf).abs());
+        }
+        // samples outside the support have an expected count of 0
+        pooled_count += histogram.total() - in_support;
+        if pooled_count > 0 || pooled_expected > 0.0 {
+            chi_sq += (pooled_count as f64 - pooled_expected).powi(2)
+                / pooled_expected.max(f64::MIN_POSITIVE);
+            bins += 1;
+        }
+        let df = bins.saturating_sub(1).max(1);
+        let sqrt_n = n.sqrt();
+        Ok(Self {
+            chi_sq,
+            df,
+            chi_sq_p_value: 1.0 - chi_sq_cdf(chi_sq, df),
+            ks,
+            ks_p_value: kolmogorov_p_value((sqrt_n + 0.12 + 0.11 / sqrt_n) * ks),
+        })
+    }
+
+    /// Fail if either test rejects the expected distribution at `significance`.
+    pub fn check(&self, significance: f64) -> Result<()> {
+        if self.chi_sq_p_value < significance {
+            anyhow::bail!(
+                "Chi-squared test rejected the distribution: statistic {} with {} degrees of freedom, p-value {}",
+                self.chi_sq,
+                self.df,
+                self.chi_sq_p_value
+            );
+        }
+        if self.ks_p_value < significance {
+            anyhow::bail!(
+                "Kolmogorov-Smirnov test rejected the distribution: statistic {}, p-value {}",
+                self.ks,
+                self.ks_p_value
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Draw `samples` values from `sampler` and test them against `pmf` over `support` at
+/// `significance`. Returns the test results on success.
+pub fn test_sampler(
+    samples: usize,
+    significance: f64,
+    support: RangeInclusive<i64>,
+    pmf: impl Fn(i64) -> f64,
+    mut sampler: impl FnMut() -> i64,
+) -> Result<GoodnessOfFit> {
+    let histogram = (0..samples).map(|_| sampler()).collect::<Histogram>();
+    let fit = GoodnessOfFit::new(&histogram, support, pmf)?;
+    fit.check(significance)?;
+    Ok(fit)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+
+    use super::*;
+
+    #[test]
+    fn gamma_function() {
+        // Gamma(n) = (n - 1)!
+        let mut factorial = 1f64;
+        for n in 1..20 {
+            assert!((ln_gamma(n as f64) - factorial.ln()).abs() < 1e-12);
+            factorial *= n as f64;
+        }
+        // Gamma(1/2) = sqrt(pi)
+        assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-12);
+        // P(1, x) = 1 - exp(-x)
+        for x in [0.1, 1.0, 2.5, 10.0] {
+            assert!((regularized_gamma_p(1.0, x) - (1.0 - f64::exp(-x))).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn chi_sq_quantiles() {
+        // (df, 0.95 quantile, 0.99 quantile) from standard tables
+        for (df, q95, q99) in [
+            (1, 3.841, 6.635),
+            (2, 5.991, 9.210),
+            (10, 18.307, 23.209),
+            (50, 67.505, 76.154),
+            (100, 124.342, 135.807),
+            (300, 341.395, 359.906),
+        ] {
+            assert!((chi_sq_quantile(0.95, df) - q95).abs() < 1e-3);
+            assert!((chi_sq_quantile(0.99, df) - q99).abs() < 1e-3);
+        }
+        // df beyond any table
+        let q = chi_sq_quantile(0.95, 5000);
+        assert!((chi_sq_cdf(q, 5000) - 0.95).abs() < 1e-9);
+    }
+
+    #[test]
+    fn histogram() {
+        let histogram = [1, 2, 2, 3, 3, 3].into_iter().collect::<Histogram>();
+        assert_eq!(histogram.total(), 6);
+        assert_eq!(histogram.count(3), 3);
+        assert_eq!(histogram.count(4), 0);
+        assert_eq!(histogram.frequency(2), 1.0 / 3.0);
+        assert!((histogram.mean() - 14.0 / 6.0).abs() < 1e-12);
+        assert!((histogram.variance() - 5.0 / 9.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn goodness_of_fit_uniform_field() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+        // sample_rand in the seven field
+        test_sampler(
+            100_000,
+            0.001,
+            -3..=3,
+            |_| 1.0,
+            || SevenScalar::sample_rand(rng).displacement() as i64,
+        )?;
+        // sample_rand in the oxfoi field, bucketed by the top 6 bits
+        let fit = test_sampler(
+            100_000,
+            0.001,
+            0..=63,
+            |_| 1.0,
+            || {
+                let v: u128 = OxfoiScalar::sample_rand(rng).into();
+                (v * 64 / OxfoiScalar::CARDINALITY) as i64
+            },
+        )?;
+        assert_eq!(fit.df, 63);
+        Ok(())
+    }
+
+    #[test]
+    fn goodness_of_fit_gaussian() -> Result<()> {
+        type Field = OxfoiScalar;
+        let rng = &mut crate::test::test_rng();
+
+        let sigma = 3.2;
+        let cdt = GaussianCDT::new::<Field>(sigma);
+        let tail = cdt.tail_bound() as i64;
+        let pdf = |x: i64| f64::exp(-(x as f64).powi(2) / (2.0 * sigma * sigma));
+        test_sampler(100_000, 0.001, -tail..=tail, pdf, || {
+            cdt.sample_ct::<Field, _>(rng).displacement() as i64
+        })?;
+        let exact = ExactGaussian::new(1024, 100);
+        test_sampler(100_000, 0.001, -tail..=tail, pdf, || exact.sample_int(rng))?;
+        Ok(())
+    }
+
+    #[test]
+    fn goodness_of_fit_rejects() {
+        let rng = &mut crate::test::test_rng();
+        let pdf = |x: i64| f64::exp(-(x as f64).powi(2) / 8.0);
+        // sigma 2.2 instead of 2
+        let wrong = ExactGaussian::new(484, 100);
+        let result = test_sampler(100_000, 0.001, -16..=16, pdf, || wrong.sample_int(rng));
+        assert!(result.is_err());
+        // a shifted distribution is caught by the ks test
+        let histogram = (0..10_000)
+            .map(|_| rng.random_range(1..=10))
+            .collect::<Histogram>();
+        let fit = GoodnessOfFit::new(&histogram, 0..=9, |_| 1.0).unwrap();
+        assert!(fit.ks_p_value < 0.001);
+        assert!(fit.check(0.001).is_err());
+        // there is nothing to test without samples
+        assert!(GoodnessOfFit::new(&Histogram::new(), 0..=9, |_| 1.0).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use crate::*;
+
+/// Sample an integer uniformly from `[0, bound)`.
+///
+/// Draws a uniform `u128` and rejects the lowest `2^128 mod bound` values, so the remaining range
+/// is a whole number of copies of `[0, bound)` and reducing modulo `bound` is unbiased. Rejection
+/// happens with probability below `bound / 2^128`.
+pub fn uniform_below<R: Rng + ?Sized>(bound: u128, rng: &mut R) -> u128 {
+    assert!(bound > 0, "uniform_below: bound must be positive");
+    // 2^128 mod bound
+    let threshold = bound.wrapping_neg() % bound;
+    loop {
+        let v = rng.random::<u128>();
+        if v >= threshold {
+            return v % bound;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TOTAL_SAMPLES: usize = 100_000;
+
+    /// Chi-squared and KS test of `sample` against the uniform distribution on `support`.
+    fn check_uniform(
+        support: std::ops::RangeInclusive<i64>,
+        sample: impl FnMut() -> i64,
+    ) -> Result<()> {
+        test_sampler(TOTAL_SAMPLES, 0.001, support, |_| 1.0, sample)?;
+        Ok(())
+    }
+
+    #[test]
+    fn uniform_rejection_threshold() {
+        /// Rng returning a fixed sequence of `u64` values.
+        struct Sequence(Vec<u64>);
+        impl rand::RngCore for Sequence {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+            fn next_u64(&mut self) -> u64 {
+                self.0.remove(0)
+            }
+            fn fill_bytes(&mut self, dst: &mut [u8]) {
+                for chunk in dst.chunks_mut(8) {
+                    let bytes = self.next_u64().to_le_bytes();
+                    chunk.copy_from_slice(&bytes[..chunk.len()]);
+                }
+            }
+        }
+
+        // 2^128 mod 7 = 2, so a draw of 0 is rejected
+        let rng = &mut Sequence(vec![0, 0, u64::MAX, u64::MAX]);
+        assert_eq!(uniform_below(7, rng), u128::MAX % 7);
+        // 2^128 mod (2^127 + 1) = 2^127 - 1, so about half of all draws are rejected
+        let bound = (1 << 127) + 1;
+        let rng = &mut Sequence(vec![5, 5, 1 << 62, 1 << 62, u64::MAX, u64::MAX]);
+        assert_eq!(uniform_below(bound, rng), u128::MAX - bound);
+        assert!(rng.0.is_empty());
+        // powers of two never reject
+        assert_eq!(uniform_below(1 << 64, &mut Sequence(vec![0, 0])), 0);
+    }
+
+    #[test]
+    fn sample_rand_uniform() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+        check_uniform(0..=1, || {
+            let v: u128 = BinaryScalar::sample_rand(rng).into();
+            v as i64
+        })?;
+        check_uniform(0..=6, || {
+            let v: u128 = SevenScalar::sample_rand(rng).into();
+            v as i64
+        })?;
+        // bucket by the top 6 bits, and the lowest 6 bits separately
+        check_uniform(0..=63, || {
+            let v: u128 = OxfoiScalar::sample_rand(rng).into();
+            (v * 64 / OxfoiScalar::CARDINALITY) as i64
+        })?;
+        check_uniform(0..=63, || {
+            let v: u128 = OxfoiScalar::sample_rand(rng).into();
+            (v % 64) as i64
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn sample_below_uniform() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+        for bound in [1, 2, 5, 7] {
+            check_uniform(0..=bound as i64 - 1, || {
+                let v: u128 = SevenScalar::sample_below(bound, rng).into();
+                v as i64
+            })?;
+        }
+        check_uniform(0..=2, || {
+            let v: u128 = OxfoiScalar::sample_below(3, rng).into();
+            v as i64
+        })?;
+        check_uniform(0..=99, || {
+            let v: u128 = OxfoiScalar::sample_below(1 << 40, rng).into();
+            assert!(v < 1 << 40);
+            ((v * 100) >> 40) as i64
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn sample_centered_uniform() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+        check_uniform(-3..=3, || {
+            SevenScalar::sample_centered(3, rng).displacement() as i64
+        })?;
+        check_uniform(-1..=1, || {
+            SevenScalar::sample_centered(1, rng).displacement() as i64
+        })?;
+        check_uniform(0..=0, || {
+            BinaryScalar::sample_centered(0, rng).displacement() as i64
+        })?;
+        for bound in [1, 10, 1000] {
+            check_uniform(-(bound as i64)..=bound as i64, || {
+                OxfoiScalar::sample_centered(bound, rng).displacement() as i64
+            })?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn sample_below_outside_field() {
+        SevenScalar::sample_below(8, &mut crate::test::test_rng());
+    }
+
+    #[test]
+    #[should_panic]
+    fn sample_centered_outside_field() {
+        // {-1, 0, 1} has three values but the binary field only two
+        BinaryScalar::sample_centered(1, &mut crate::test::test_rng());
+    }
+}
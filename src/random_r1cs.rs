@@ -0,0 +1,249 @@
+use anyhow::Result;
+use rand::seq::index;
+
+use super::*;
+
+/// Parameters for sampling random satisfiable R1CS instances.
+///
+/// The witness is laid out as `[1, public inputs, private inputs, outputs]` with one output
+/// variable per constraint. Constraint `i` has `density` nonzero entries in each of its `A`, `B`
+/// and `C` rows, over the constant, the inputs and the outputs of earlier constraints, and
+/// determines its own output. Later constraints therefore depend on earlier ones.
+#[derive(Clone, Debug)]
+pub struct RandomR1CS {
+    constraints: usize,
+    variables: usize,
+    density: usize,
+    public_inputs: usize,
+}
+
+/// A sampled instance along with a satisfying witness.
+#[derive(Clone, Debug)]
+pub struct RandomR1CSInstance<E: Element> {
+    pub r1cs: R1CS<E>,
+    pub witness: Vector<E>,
+    public_inputs: usize,
+}
+
+impl RandomR1CS {
+    /// Instances with `constraints` constraints over `variables` variables, including the
+    /// constant one. Defaults to 2 nonzero entries per row and no public inputs.
+    ///
+    /// Panics if there are fewer than `constraints + 1` variables.
+    pub fn new(constraints: usize, variables: usize) -> Self {
+        assert!(
+            variables > constraints,
+            "RandomR1CS needs at least {} variables for {} constraints, got {}",
+            constraints + 1,
+            constraints,
+            variables
+        );
+        Self {
+            constraints,
+            variables,
+            density: 2,
+            public_inputs: 0,
+        }
+    }
+
+    /// Number of nonzero entries in each row of `A`, `B` and `C`. Rows with fewer available
+    /// variables are dense.
+    pub fn density(mut self, density: usize) -> Self {
+        assert!(density > 0, "RandomR1CS density must be nonzero");
+        self.density = density;
+        self
+    }
+
+    /// Number of inputs directly following the constant one that are public.
+    ///
+    /// Panics if there are not enough input variables.
+    pub fn public_inputs(mut self, public_inputs: usize) -> Self {
+        assert!(
+            public_inputs < self.variables - self.constraints,
+            "RandomR1CS has {} input variables, cannot make {} public",
+            self.variables - self.constraints - 1,
+            public_inputs
+        );
+        self.public_inputs = public_inputs;
+        self
+    }
+
+    pub fn sample<E: Element, R: Rng>(&self, rng: &mut R) -> RandomR1CSInstance<E> {
+        let inputs = self.variables - self.constraints;
+        let mut r1cs = R1CS::identity(self.constraints, self.variables);
+        for i in 0..self.constraints {
+            let output = inputs + i;
+            for row in [&mut r1cs.a[i], &mut r1cs.b[i]] {
+                for j in index::sample(rng, output, self.density.min(output)) {
+                    row[j] = nonzero(rng);
+                }
+            }
+            // the output is always present in C, the remaining entries are earlier variables
+            let row = &mut r1cs.c[i];
+            row[output] = nonzero(rng);
+            for j in index::sample(rng, output, (self.density - 1).min(output)) {
+                row[j] = nonzero(rng);
+            }
+        }
+
+        let mut instance = RandomR1CSInstance {
+            r1cs,
+            witness: Vector::random(self.variables, rng),
+            public_inputs: self.public_inputs,
+        };
+        instance.witness[0] = E::one();
+        instance.solve_outputs(&[]);
+        instance
+    }
+}
+
+impl<E: Element> R1CS<E> {
+    /// Sample an R1CS with `constraints` constraints over `variables` variables with `density`
+    /// nonzero entries per row, along with a satisfying witness. Variable 0 of the witness is
+    /// the constant one. See `RandomR1CS` for more control over the instance.
+    pub fn random_satisfiable<R: Rng>(
+        constraints: usize,
+        variables: usize,
+        density: usize,
+        rng: &mut R,
+    ) -> (Self, Vector<E>) {
+        let instance = RandomR1CS::new(constraints, variables)
+            .density(density)
+            .sample(rng);
+        (instance.r1cs, instance.witness)
+    }
+}
+
+impl<E: Element> RandomR1CSInstance<E> {
+    pub fn public_inputs(&self) -> usize {
+        self.public_inputs
+    }
+
+    /// The constant one followed by the public inputs.
+    pub fn public_witness(&self) -> &[E] {
+        &self.witness[..self.public_inputs + 1]
+    }
+
+    /// The private inputs and outputs.
+    pub fn private_witness(&self) -> &[E] {
+        &self.witness[self.public_inputs + 1..]
+    }
+
+    /// A witness with the same inputs that fails exactly `k` randomly chosen constraints.
+    pub fn near_satisfying<R: Rng>(&self, k: usize, rng: &mut R) -> Result<Vector<E>> {
+        let constraints = self.r1cs.dimension().0;
+        if k > constraints {
+            anyhow::bail!(
+                "RandomR1CSInstance cannot fail {} of {} constraints",
+                k,
+                constraints
+            );
+        }
+        let mut out = self.clone();
+        out.solve_outputs(&index::sample(rng, constraints, k).into_vec());
+        Ok(out.witness)
+    }
+
+    /// Compute constraint outputs in order. Outputs of constraints in `failing` are offset by a
+    /// nonzero value after solving, later constraints are solved against the offset values.
+    fn solve_outputs(&mut self, failing: &[usize]) {
+        let (constraints, variables) = self.r1cs.dimension();
+        let inputs = variables - constraints;
+        for i in 0..constraints {
+            let output = inputs + i;
+            self.witness[output] = E::zero();
+            let ab = Vector::from(self.r1cs.a.row(i)).inner(&self.witness)
+                * Vector::from(self.r1cs.b.row(i)).inner(&self.witness);
+            let rest = Vector::from(self.r1cs.c.row(i)).inner(&self.witness);
+            let coef = self.r1cs.c[i][output]
+                .inverse()
+                .expect("RandomR1CS output coefficient is nonzero");
+            self.witness[output] = (ab - rest) * coef;
+            if failing.contains(&i) {
+                self.witness[output] += E::one();
+            }
+        }
+    }
+}
+
+fn nonzero<E: Element, R: Rng>(rng: &mut R) -> E {
+    loop {
+        let v = E::sample_rand(rng);
+        if !v.is_zero() {
+            return v;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn random_satisfiable() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        for (constraints, variables, density) in [(1, 2, 1), (10, 20, 3), (32, 40, 8), (5, 6, 10)] {
+            let (r1cs, witness) =
+                R1CS::<Field>::random_satisfiable(constraints, variables, density, rng);
+            assert_eq!(r1cs.dimension(), (constraints, variables));
+            assert_eq!(witness[0], Field::one());
+            assert!(r1cs.eval(&witness)?.is_zero());
+            let inputs = variables - constraints;
+            for (i, m) in [&r1cs.a, &r1cs.b, &r1cs.c].iter().enumerate() {
+                for (row, entries) in m.iter().enumerate() {
+                    let nonzero = entries.iter().filter(|v| !v.is_zero()).count();
+                    let available = inputs + row + usize::from(i == 2);
+                    assert_eq!(nonzero, density.min(available));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn random_satisfiable_small_fields() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness) = R1CS::<SevenScalar>::random_satisfiable(12, 20, 3, rng);
+        assert!(r1cs.eval(&witness)?.is_zero());
+        let (r1cs, witness) = R1CS::<BinaryScalar>::random_satisfiable(12, 20, 3, rng);
+        assert!(r1cs.eval(&witness)?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn random_public_split() {
+        let rng = &mut crate::test::test_rng();
+
+        let instance = RandomR1CS::new(8, 16)
+            .public_inputs(3)
+            .sample::<Field, _>(rng);
+        assert_eq!(instance.public_inputs(), 3);
+        assert_eq!(instance.public_witness().len(), 4);
+        assert_eq!(instance.private_witness().len(), 12);
+        assert_eq!(instance.public_witness()[0], Field::one());
+    }
+
+    #[test]
+    fn near_satisfying() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let instance = RandomR1CS::new(20, 30).density(4).sample::<Field, _>(rng);
+        for k in [0, 1, 5, 20] {
+            let witness = instance.near_satisfying(k, rng)?;
+            assert_eq!(witness[..10], instance.witness[..10]);
+            let failed = instance
+                .r1cs
+                .eval(&witness)?
+                .iter()
+                .filter(|v| !v.is_zero())
+                .count();
+            assert_eq!(failed, k);
+        }
+        assert!(instance.near_satisfying(21, rng).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::Result;
+
+use super::*;
+
+/// A relaxed R1CS instance, `(A * z) ∘ (B * z) = u * (C * z) + E`, for a scalar `u` and an error
+/// vector `E`. A plain R1CS is the relaxed instance with `u = 1` and `E = 0`.
+///
+/// Section 4 of https://eprint.iacr.org/2021/370.pdf
+#[derive(Clone, Debug)]
+pub struct RelaxedR1CS<E: Element> {
+    pub r1cs: R1CS<E>,
+    pub u: E,
+    pub error: Vector<E>,
+}
+
+impl<E: Element> From<R1CS<E>> for RelaxedR1CS<E> {
+    fn from(r1cs: R1CS<E>) -> Self {
+        let height = r1cs.dimension().0;
+        Self {
+            r1cs,
+            u: E::one(),
+            error: Vector::new(height),
+        }
+    }
+}
+
+impl<E: Element> RelaxedR1CS<E> {
+    /// Evaluate `(A * z) ∘ (B * z) - u * (C * z) - E`. A witness satisfies the instance if the
+    /// output is the zero vector.
+    pub fn eval(&self, witness: &Vector<E>) -> Result<Vector<E>> {
+        self.assert_consistency()?;
+
+        let ab = (&self.r1cs.a * witness) * &(&self.r1cs.b * witness);
+        let c = (&self.r1cs.c * witness) * self.u;
+
+        Ok(ab - c - &self.error)
+    }
+
+    pub fn dimension(&self) -> (usize, usize) {
+        self.r1cs.dimension()
+    }
+
+    /// Cross term `T` produced when folding witness `z_1` of an instance with scalar `u_1` with
+    /// witness `z_2` of an instance with scalar `u_2` over the same matrices,
+    /// `T = A z_1 ∘ B z_2 + A z_2 ∘ B z_1 - u_1 * C z_2 - u_2 * C z_1`.
+    pub fn cross_term(
+        r1cs: &R1CS<E>,
+        (u_1, z_1): (E, &Vector<E>),
+        (u_2, z_2): (E, &Vector<E>),
+    ) -> Vector<E> {
+        let az_1 = &r1cs.a * z_1;
+        let bz_1 = &r1cs.b * z_1;
+        let cz_1 = &r1cs.c * z_1;
+        let az_2 = &r1cs.a * z_2;
+        let bz_2 = &r1cs.b * z_2;
+        let cz_2 = &r1cs.c * z_2;
+        az_1 * &bz_2 + &(az_2 * &bz_1) - &(cz_2 * u_1) - &(cz_1 * u_2)
+    }
+
+    fn assert_consistency(&self) -> Result<()> {
+        self.r1cs.assert_consistency()?;
+        let height = self.r1cs.dimension().0;
+        if self.error.len() != height {
+            anyhow::bail!(
+                "Relaxed R1CS error vector length mismatch, expected {}, got {}",
+                height,
+                self.error.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::multiplication_r1cs;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn relaxed_r1cs_from_r1cs() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness) = multiplication_r1cs::<Field, _>(5, 12, rng);
+        let relaxed = RelaxedR1CS::from(r1cs.clone());
+        assert_eq!(relaxed.eval(&witness)?, r1cs.eval(&witness)?);
+        assert!(relaxed.eval(&witness)?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn relaxed_r1cs_scaled_witness() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        // scaling a satisfying witness by s satisfies the instance with u = s
+        let (r1cs, witness) = multiplication_r1cs::<Field, _>(5, 12, rng);
+        let s = Field::sample_rand(rng);
+        let mut relaxed = RelaxedR1CS::from(r1cs);
+        relaxed.u = s;
+        assert!(relaxed.eval(&(witness.clone() * s))?.is_zero());
+        assert!(!relaxed.eval(&witness)?.is_zero());
+
+        // the error vector absorbs any slack
+        let slack = relaxed.eval(&witness)?;
+        relaxed.error = slack;
+        assert!(relaxed.eval(&witness)?.is_zero());
+
+        relaxed.error = Vector::new(4);
+        assert!(relaxed.eval(&witness).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn relaxed_r1cs_cross_term() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, z_1) = multiplication_r1cs::<Field, _>(6, 12, rng);
+        let z_2 = Vector::random(12, rng);
+        let u_1 = Field::sample_rand(rng);
+        let u_2 = Field::sample_rand(rng);
+        let r = Field::sample_rand(rng);
+        let mut first = RelaxedR1CS::from(r1cs.clone());
+        first.u = u_1;
+        first.error = first.eval(&z_1)? + &first.error;
+        let mut second = RelaxedR1CS::from(r1cs.clone());
+        second.u = u_2;
+        second.error = second.eval(&z_2)? + &second.error;
+
+        let t = RelaxedR1CS::cross_term(&r1cs, (u_1, &z_1), (u_2, &z_2));
+        let folded = RelaxedR1CS {
+            r1cs,
+            u: u_1 + r * u_2,
+            error: first.error.clone() + &(t * r) + &(second.error.clone() * (r * r)),
+        };
+        assert!(folded.eval(&(z_1 + &(z_2 * r)))?.is_zero());
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+use anyhow::Result;
+
+use super::*;
+
+/// A Spartan style argument that a committed witness `z` satisfies an R1CS instance,
+/// `(A * z) ∘ (B * z) = C * z`.
+///
+/// https://eprint.iacr.org/2019/550.pdf
+///
+/// The prover commits to the multilinear extension of `z` and runs two sumchecks. The first
+/// reduces the R1CS relation at a random row to claims about `A * z`, `B * z` and `C * z`, the
+/// second reduces those claims to a single evaluation of the witness. The verifier evaluates the
+/// multilinear extensions of the R1CS matrices directly, so verification is linear in the size of
+/// the matrices but does not re-evaluate the constraints.
+#[derive(Clone, Debug)]
+pub struct SpartanProof<E: Element, C: MultilinearCommitment<E>> {
+    pub witness_commitment: C::Commitment,
+    /// Sumcheck over rows of `eq(tau, x) * (Az(x) * Bz(x) - Cz(x))`.
+    pub outer: SumcheckProof<E>,
+    /// Claimed evaluations of the multilinear extensions of `A * z`, `B * z` and `C * z` at the
+    /// point chosen in the outer sumcheck.
+    pub evaluations: (E, E, E),
+    /// Sumcheck over columns of `M(r_x, y) * z(y)` for a random combination `M` of A, B and C.
+    pub inner: SumcheckProof<E>,
+    pub witness_evaluation: E,
+    pub witness_proof: C::EvaluationProof,
+}
+
+impl<E: Element, C: MultilinearCommitment<E>> SpartanProof<E, C> {
+    /// Prove that `witness` satisfies `r1cs`, committing to the witness with `pcs`. The witness
+    /// commitment must accept polynomials in `log2(width)` variables, rounded up.
+    pub fn prove<H: ElementHasher<E> + Default, R: Rng>(
+        r1cs: &R1CS<E>,
+        witness: &Vector<E>,
+        pcs: &C,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let width = r1cs.dimension().1;
+        if witness.len() != width {
+            anyhow::bail!(
+                "Spartan witness has length {}, expected {}",
+                witness.len(),
+                width
+            );
+        }
+        let col_vars = width.next_power_of_two().trailing_zeros() as usize;
+        if pcs.num_vars() != col_vars {
+            anyhow::bail!(
+                "Spartan witness commitment accepts {} variables, expected {}",
+                pcs.num_vars(),
+                col_vars
+            );
+        }
+        if !r1cs.eval(witness)?.is_zero() {
+            anyhow::bail!("Spartan witness does not satisfy the R1CS");
+        }
+        Ok(Self::prove_unchecked::<H, R>(r1cs, witness, pcs, rng))
+    }
+
+    fn prove_unchecked<H: ElementHasher<E> + Default, R: Rng>(
+        r1cs: &R1CS<E>,
+        witness: &Vector<E>,
+        pcs: &C,
+        rng: &mut R,
+    ) -> Self {
+        let mut hasher = H::default();
+        let z = MultilinearPolynomial::from_vector_padded(witness.clone());
+        let (witness_commitment, opening) = pcs.commit(&z, rng);
+        let (row_vars, _) = Self::write_instance(r1cs, &witness_commitment, &mut hasher);
+
+        let tau = (0..row_vars)
+            .map(|_| hasher.challenge())
+            .collect::<Vec<_>>();
+        let az = MultilinearPolynomial::from_vector_padded(&r1cs.a * witness);
+        let bz = MultilinearPolynomial::from_vector_padded(&r1cs.b * witness);
+        let cz = MultilinearPolynomial::from_vector_padded(&r1cs.c * witness);
+        let mut outer_poly = SumOfProducts::new(row_vars);
+        let eq = outer_poly.add_polynomial(MultilinearPolynomial::eq(&tau));
+        let a = outer_poly.add_polynomial(az.clone());
+        let b = outer_poly.add_polynomial(bz.clone());
+        let c = outer_poly.add_polynomial(cz.clone());
+        outer_poly.add_product(E::one(), vec![eq, a, b]);
+        outer_poly.add_product(E::negone(), vec![eq, c]);
+        let (outer, r_x) = SumcheckProof::prove(outer_poly, &mut hasher);
+
+        let evaluations = (az.evaluate(&r_x), bz.evaluate(&r_x), cz.evaluate(&r_x));
+        let combined = Self::combine_rows(r1cs, &r_x, &mut hasher, evaluations).0;
+        let inner_poly = SumOfProducts::from_product(vec![combined, z.clone()]);
+        let (inner, r_y) = SumcheckProof::prove(inner_poly, &mut hasher);
+
+        let witness_evaluation = z.evaluate(&r_y);
+        let witness_proof = pcs.open(&z, &opening, &r_y);
+        Self {
+            witness_commitment,
+            outer,
+            evaluations,
+            inner,
+            witness_evaluation,
+            witness_proof,
+        }
+    }
+
+    /// Verify that the committed witness satisfies `r1cs`.
+    pub fn verify<H: ElementHasher<E> + Default>(&self, r1cs: &R1CS<E>, pcs: &C) -> Result<()> {
+        r1cs.assert_consistency()?;
+        let mut hasher = H::default();
+        let (row_vars, col_vars) =
+            Self::write_instance(r1cs, &self.witness_commitment, &mut hasher);
+        if pcs.num_vars() != col_vars {
+            anyhow::bail!(
+                "Spartan witness commitment accepts {} variables, expected {}",
+                pcs.num_vars(),
+                col_vars
+            );
+        }
+
+        let tau = (0..row_vars)
+            .map(|_| hasher.challenge())
+            .collect::<Vec<_>>();
+        let (r_x, expected) = self
+            .outer
+            .verify(E::zero(), row_vars, 3, &mut hasher)
+            .map_err(|e| anyhow::anyhow!("Spartan outer sumcheck failed: {e}"))?;
+        let (v_a, v_b, v_c) = self.evaluations;
+        if MultilinearPolynomial::eq_evaluate(&tau, &r_x) * (v_a * v_b - v_c) != expected {
+            anyhow::bail!("Spartan outer sumcheck final evaluation mismatch");
+        }
+
+        let (combined, claim) = Self::combine_rows(r1cs, &r_x, &mut hasher, self.evaluations);
+        let (r_y, expected) = self
+            .inner
+            .verify(claim, col_vars, 2, &mut hasher)
+            .map_err(|e| anyhow::anyhow!("Spartan inner sumcheck failed: {e}"))?;
+        if combined.evaluate(&r_y) * self.witness_evaluation != expected {
+            anyhow::bail!("Spartan inner sumcheck final evaluation mismatch");
+        }
+
+        pcs.verify(
+            &self.witness_commitment,
+            &r_y,
+            self.witness_evaluation,
+            &self.witness_proof,
+        )
+        .map_err(|e| anyhow::anyhow!("Spartan witness opening failed: {e}"))
+    }
+
+    /// Write the R1CS and witness commitment into the transcript. Returns the number of row and
+    /// column variables.
+    fn write_instance<H: ElementHasher<E>>(
+        r1cs: &R1CS<E>,
+        commitment: &C::Commitment,
+        hasher: &mut H,
+    ) -> (usize, usize) {
+        let (height, width) = r1cs.dimension();
+        r1cs.write_transcript(hasher);
+        C::write_commitment(commitment, hasher);
+        (
+            height.next_power_of_two().trailing_zeros() as usize,
+            width.next_power_of_two().trailing_zeros() as usize,
+        )
+    }
+
+    /// Draw challenges `rho` and build the multilinear polynomial in the column variables
+    /// `sum_i eq(r_x, i) * (rho_a * A[i] + rho_b * B[i] + rho_c * C[i])`. Returns the polynomial
+    /// and the claimed sum `rho_a * v_a + rho_b * v_b + rho_c * v_c`.
+    fn combine_rows<H: ElementHasher<E>>(
+        r1cs: &R1CS<E>,
+        r_x: &[E],
+        hasher: &mut H,
+        evaluations: (E, E, E),
+    ) -> (MultilinearPolynomial<E>, E) {
+        let (v_a, v_b, v_c) = evaluations;
+        hasher.write_element(&v_a);
+        hasher.write_element(&v_b);
+        hasher.write_element(&v_c);
+        let rho_a = hasher.challenge();
+        let rho_b = hasher.challenge();
+        let rho_c = hasher.challenge();
+
+        let height = r1cs.dimension().0;
+        let eq = MultilinearPolynomial::eq(r_x);
+        let eq_rows = Vector::from(&eq.evaluations()[..height]);
+        let combined = (&eq_rows * &r1cs.a) * rho_a
+            + &((&eq_rows * &r1cs.b) * rho_b)
+            + &((&eq_rows * &r1cs.c) * rho_c);
+        (
+            MultilinearPolynomial::from_vector_padded(combined),
+            rho_a * v_a + rho_b * v_b + rho_c * v_c,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TestHasher;
+    use crate::test::multiplication_r1cs;
+
+    type Field = OxfoiScalar;
+
+    #[test]
+    fn spartan_prove_verify() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        for (height, width) in [(1, 2), (4, 8), (5, 12), (8, 16)] {
+            let (r1cs, witness) = multiplication_r1cs::<Field, _>(height, width, rng);
+            assert!(r1cs.eval(&witness)?.is_zero());
+            let num_vars = width.next_power_of_two().trailing_zeros() as usize;
+            let pcs = SISMultilinear::setup(num_vars, 8, rng);
+            let proof = SpartanProof::prove::<TestHasher, _>(&r1cs, &witness, &pcs, rng)?;
+            proof.verify::<TestHasher>(&r1cs, &pcs)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn spartan_rejects_invalid() -> Result<()> {
+        let rng = &mut crate::test::test_rng();
+
+        let (r1cs, witness) = multiplication_r1cs::<Field, _>(6, 16, rng);
+        let pcs = SISMultilinear::setup(4, 8, rng);
+
+        let mut bad_witness = witness.clone();
+        bad_witness[8] += Field::one();
+        assert!(SpartanProof::prove::<TestHasher, _>(&r1cs, &bad_witness, &pcs, rng).is_err());
+        let forged = SpartanProof::prove_unchecked::<TestHasher, _>(&r1cs, &bad_witness, &pcs, rng);
+        assert!(forged.verify::<TestHasher>(&r1cs, &pcs).is_err());
+
+        let proof = SpartanProof::prove::<TestHasher, _>(&r1cs, &witness, &pcs, rng)?;
+        proof.verify::<TestHasher>(&r1cs, &pcs)?;
+
+        let mut tampered = proof.clone();
+        tampered.evaluations.0 += Field::one();
+        assert!(tampered.verify::<TestHasher>(&r1cs, &pcs).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.witness_evaluation += Field::one();
+        assert!(tampered.verify::<TestHasher>(&r1cs, &pcs).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.inner.round_polynomials[0][1] += Field::one();
+        assert!(tampered.verify::<TestHasher>(&r1cs, &pcs).is_err());
+
+        // the proof does not verify against a different instance
+        let (other, _) = multiplication_r1cs::<Field, _>(6, 16, rng);
+        assert!(proof.verify::<TestHasher>(&other, &pcs).is_err());
+        // and its challenges depend on the matrices, not just the shape
+        let transcript = |r1cs: &R1CS<Field>| {
+            let mut hasher = TestHasher::default();
+            r1cs.write_transcript(&mut hasher);
+            ElementHasher::<Field>::finish(&hasher)
+        };
+        assert_ne!(transcript(&r1cs), transcript(&other));
+
+        // mismatched witness and commitment sizes are errors
+        let short = Vector::from(&witness.as_slice()[..15]);
+        assert!(SpartanProof::prove::<TestHasher, _>(&r1cs, &short, &pcs, rng).is_err());
+        let wide_pcs = SISMultilinear::setup(5, 8, rng);
+        assert!(SpartanProof::prove::<TestHasher, _>(&r1cs, &witness, &wide_pcs, rng).is_err());
+        assert!(proof.verify::<TestHasher>(&r1cs, &wide_pcs).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,404 @@
+use anyhow::Result;
+
+use super::*;
+
+/// A sum of products of multilinear polynomials, `sum_i c_i * prod_{j in S_i} P_j`, over a shared
+/// set of variables. Each product is a list of indices into the stored polynomials so a
+/// polynomial may appear in several products.
+#[derive(Debug, Clone)]
+pub struct SumOfProducts<E: Element> {
+    num_vars: usize,
+    polynomials: Vec<MultilinearPolynomial<E>>,
+    products: Vec<(E, Vec<usize>)>,
+}
+
+impl<E: Element> SumOfProducts<E> {
+    pub fn new(num_vars: usize) -> Self {
+        Self {
+            num_vars,
+            polynomials: Vec::new(),
+            products: Vec::new(),
+        }
+    }
+
+    /// A single product of multilinear polynomials with coefficient one.
+    pub fn from_product(polynomials: Vec<MultilinearPolynomial<E>>) -> Self {
+        let num_vars = polynomials
+            .first()
+            .map(|p| p.num_vars())
+            .unwrap_or_default();
+        let mut out = Self::new(num_vars);
+        let indices = polynomials
+            .into_iter()
+            .map(|p| out.add_polynomial(p))
+            .collect();
+        out.add_product(E::one(), indices);
+        out
+    }
+
+    /// Store a polynomial and return the index used to refer to it in products.
+    ///
+    /// Panics if the polynomial has a different number of variables.
+    pub fn add_polynomial(&mut self, polynomial: MultilinearPolynomial<E>) -> usize {
+        assert_eq!(
+            polynomial.num_vars(),
+            self.num_vars,
+            "SumOfProducts::add_polynomial polynomial has wrong number of variables"
+        );
+        self.polynomials.push(polynomial);
+        self.polynomials.len() - 1
+    }
+
+    /// Add the term `coefficient * prod_{j in indices} P_j`.
+    ///
+    /// Panics if an index does not refer to a stored polynomial.
+    pub fn add_product(&mut self, coefficient: E, indices: Vec<usize>) {
+        for i in &indices {
+            assert!(
+                *i < self.polynomials.len(),
+                "SumOfProducts::add_product unknown polynomial index {}",
+                i
+            );
+        }
+        self.products.push((coefficient, indices));
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Degree of the sum in each variable, the size of the largest product.
+    pub fn degree(&self) -> usize {
+        self.products
+            .iter()
+            .map(|(_, indices)| indices.len())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Evaluate the sum of products at an arbitrary point.
+    pub fn evaluate(&self, point: &[E]) -> E {
+        let evals = self
+            .polynomials
+            .iter()
+            .map(|p| p.evaluate(point))
+            .collect::<Vec<_>>();
+        self.combine(&evals)
+    }
+
+    /// Sum of the evaluations over the boolean hypercube.
+    pub fn sum(&self) -> E {
+        let mut out = E::zero();
+        let mut evals = vec![E::zero(); self.polynomials.len()];
+        for x in 0..(1 << self.num_vars) {
+            for (eval, p) in evals.iter_mut().zip(&self.polynomials) {
+                *eval = p.evaluations()[x];
+            }
+            out += self.combine(&evals);
+        }
+        out
+    }
+
+    /// Combine evaluations of each stored polynomial at a single point.
+    fn combine(&self, evals: &[E]) -> E {
+        let mut out = E::zero();
+        for (coefficient, indices) in &self.products {
+            let mut term = *coefficient;
+            for i in indices {
+                term *= evals[*i];
+            }
+            out += term;
+        }
+        out
+    }
+
+    /// Fix the first variable of every stored polynomial to `r`.
+    fn bind_in_place(&mut self, r: E) {
+        for p in self.polynomials.iter_mut() {
+            p.bind_in_place(r);
+        }
+        self.num_vars -= 1;
+    }
+
+    /// Evaluate the univariate polynomial obtained by summing over all but the first variable at
+    /// the points 0..=degree.
+    fn round_polynomial(&self) -> Vector<E> {
+        let degree = self.degree();
+        let half = 1 << (self.num_vars - 1);
+        let mut out = Vector::new(degree + 1);
+        let mut evals = vec![E::zero(); self.polynomials.len()];
+        let mut steps = vec![E::zero(); self.polynomials.len()];
+        for x in 0..half {
+            for ((eval, step), p) in evals
+                .iter_mut()
+                .zip(steps.iter_mut())
+                .zip(&self.polynomials)
+            {
+                let low = p.evaluations()[2 * x];
+                let high = p.evaluations()[2 * x + 1];
+                *eval = low;
+                *step = high - low;
+            }
+            // walk each polynomial along the line from its evaluation at t = 0
+            for t in 0..=degree {
+                if t > 0 {
+                    for (eval, step) in evals.iter_mut().zip(&steps) {
+                        *eval += *step;
+                    }
+                }
+                out[t] += self.combine(&evals);
+            }
+        }
+        out
+    }
+}
+
+/// A non-interactive sumcheck proof. Each round polynomial is given by its evaluations at
+/// 0..=degree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SumcheckProof<E: Element> {
+    pub round_polynomials: Vec<Vector<E>>,
+}
+
+impl<E: Element> SumcheckProof<E> {
+    /// Prove the value of the sum of `polynomial` over the boolean hypercube. Challenges are
+    /// derived from `hasher`.
+    ///
+    /// Returns the proof and the random point the sum was reduced to.
+    pub fn prove<H: ElementHasher<E>>(
+        mut polynomial: SumOfProducts<E>,
+        hasher: &mut H,
+    ) -> (Self, Vec<E>) {
+        hasher.write_element(&polynomial.sum());
+        let num_vars = polynomial.num_vars();
+        let mut round_polynomials = Vec::with_capacity(num_vars);
+        let mut point = Vec::with_capacity(num_vars);
+        for _ in 0..num_vars {
+            let round_polynomial = polynomial.round_polynomial();
+            for eval in round_polynomial.iter() {
+                hasher.write_element(eval);
+            }
+            let r = hasher.challenge();
+            polynomial.bind_in_place(r);
+            round_polynomials.push(round_polynomial);
+            point.push(r);
+        }
+        (Self { round_polynomials }, point)
+    }
+
+    /// Verify the proof against a claimed sum, reducing it to a claim about a single evaluation.
+    ///
+    /// Returns the random point and the value the polynomial must take at that point. The caller
+    /// is responsible for checking this final evaluation.
+    pub fn verify<H: ElementHasher<E>>(
+        &self,
+        claimed_sum: E,
+        num_vars: usize,
+        degree: usize,
+        hasher: &mut H,
+    ) -> Result<(Vec<E>, E)> {
+        if degree == 0 {
+            anyhow::bail!("Sumcheck round polynomials must have degree at least 1");
+        }
+        if self.round_polynomials.len() != num_vars {
+            anyhow::bail!(
+                "Sumcheck proof has {} rounds, expected {}",
+                self.round_polynomials.len(),
+                num_vars
+            );
+        }
+        hasher.write_element(&claimed_sum);
+        let mut claim = claimed_sum;
+        let mut point = Vec::with_capacity(num_vars);
+        for (round, round_polynomial) in self.round_polynomials.iter().enumerate() {
+            if round_polynomial.len() != degree + 1 {
+                anyhow::bail!(
+                    "Sumcheck round {} polynomial has {} evaluations, expected {}",
+                    round,
+                    round_polynomial.len(),
+                    degree + 1
+                );
+            }
+            if round_polynomial[0] + round_polynomial[1] != claim {
+                anyhow::bail!(
+                    "Sumcheck round {} failed, g(0) + g(1) does not equal the claimed sum",
+                    round
+                );
+            }
+            for eval in round_polynomial.iter() {
+                hasher.write_element(eval);
+            }
+            let r = hasher.challenge();
+            claim = interpolate(round_polynomial, r);
+            point.push(r);
+        }
+        Ok((point, claim))
+    }
+
+    /// Verify the proof and check the final evaluation directly against `polynomial`.
+    ///
+    /// Returns the random point the sum was reduced to.
+    pub fn verify_with<H: ElementHasher<E>>(
+        &self,
+        claimed_sum: E,
+        polynomial: &SumOfProducts<E>,
+        hasher: &mut H,
+    ) -> Result<Vec<E>> {
+        let (point, expected) = self.verify(
+            claimed_sum,
+            polynomial.num_vars(),
+            polynomial.degree(),
+            hasher,
+        )?;
+        if polynomial.evaluate(&point) != expected {
+            anyhow::bail!("Sumcheck final evaluation does not match the polynomial");
+        }
+        Ok(point)
+    }
+}
+
+/// Evaluate the polynomial of degree `evals.len() - 1` taking value `evals[i]` at `i` at the
+/// point `x` using Lagrange interpolation.
+fn interpolate<E: Element>(evals: &Vector<E>, x: E) -> E {
+    let mut out = E::zero();
+    for (i, eval) in evals.iter().enumerate() {
+        let x_i = E::from(i as u128);
+        let mut numerator = E::one();
+        let mut denominator = E::one();
+        for j in (0..evals.len()).filter(|j| *j != i) {
+            let x_j = E::from(j as u128);
+            numerator *= x - x_j;
+            denominator *= x_i - x_j;
+        }
+        let inv = denominator
+            .inverse()
+            .expect("sumcheck degree is too large for the field");
+        out += *eval * numerator * inv;
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::TestHasher;
+
+    #[test]
+    fn sumcheck_product() -> Result<()> {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        for degree in 1..4 {
+            let num_vars = 5;
+            let polynomials = (0..degree)
+                .map(|_| MultilinearPolynomial::<Field>::random(num_vars, &mut rng))
+                .collect::<Vec<_>>();
+            let poly = SumOfProducts::from_product(polynomials.clone());
+            let sum = poly.sum();
+
+            let (proof, prover_point) =
+                SumcheckProof::prove(poly.clone(), &mut TestHasher::default());
+            let (point, expected) =
+                proof.verify(sum, num_vars, degree, &mut TestHasher::default())?;
+            assert_eq!(point, prover_point);
+            let mut product = Field::one();
+            for p in &polynomials {
+                product *= p.evaluate(&point);
+            }
+            assert_eq!(product, expected);
+
+            proof.verify_with(sum, &poly, &mut TestHasher::default())?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sumcheck_sum_of_products() -> Result<()> {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let num_vars = 4;
+        let mut poly = SumOfProducts::<Field>::new(num_vars);
+        let a = poly.add_polynomial(MultilinearPolynomial::random(num_vars, &mut rng));
+        let b = poly.add_polynomial(MultilinearPolynomial::random(num_vars, &mut rng));
+        let c = poly.add_polynomial(MultilinearPolynomial::random(num_vars, &mut rng));
+        poly.add_product(Field::one(), vec![a, b, c]);
+        poly.add_product(Field::negone(), vec![c]);
+        poly.add_product(Field::from(3u128), vec![a, a]);
+        assert_eq!(poly.degree(), 3);
+
+        let sum = poly.sum();
+        let (proof, _) = SumcheckProof::prove(poly.clone(), &mut TestHasher::default());
+        proof.verify_with(sum, &poly, &mut TestHasher::default())?;
+        Ok(())
+    }
+
+    #[test]
+    fn sumcheck_rejects_wrong_sum() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let num_vars = 6;
+        let poly = SumOfProducts::from_product(vec![
+            MultilinearPolynomial::<Field>::random(num_vars, &mut rng),
+            MultilinearPolynomial::<Field>::random(num_vars, &mut rng),
+        ]);
+        let sum = poly.sum();
+        let (proof, _) = SumcheckProof::prove(poly.clone(), &mut TestHasher::default());
+
+        let wrong_sum = sum + Field::one();
+        assert!(
+            proof
+                .verify_with(wrong_sum, &poly, &mut TestHasher::default())
+                .is_err()
+        );
+
+        // a prover claiming the wrong sum must send an inconsistent first round polynomial, after
+        // which the final evaluation check fails
+        let mut forged = proof.clone();
+        forged.round_polynomials[0][0] += Field::one();
+        assert!(
+            forged
+                .verify_with(wrong_sum, &poly, &mut TestHasher::default())
+                .is_err()
+        );
+        assert!(
+            forged
+                .verify_with(sum, &poly, &mut TestHasher::default())
+                .is_err()
+        );
+
+        // truncated proofs are rejected
+        let mut truncated = proof.clone();
+        truncated.round_polynomials.pop();
+        assert!(
+            truncated
+                .verify_with(sum, &poly, &mut TestHasher::default())
+                .is_err()
+        );
+
+        // constant round polynomials have no g(1) to check
+        let constant = SumcheckProof {
+            round_polynomials: vec![vec![sum].into()],
+        };
+        assert!(
+            constant
+                .verify(sum, 1, 0, &mut TestHasher::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn sumcheck_interpolate() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        // p(x) = 3x^2 + 2x + 1
+        let p = |x: Field| Field::from(3u128) * x * x + Field::from(2u128) * x + Field::one();
+        let evals = (0..3u128).map(|i| p(i.into())).collect::<Vector<_>>();
+        for _ in 0..10 {
+            let x = Field::sample_rand(&mut rng);
+            assert_eq!(interpolate(&evals, x), p(x));
+        }
+    }
+}
@@ -1,7 +1,85 @@
+use std::cell::Cell;
+use std::hash::DefaultHasher;
+use std::hash::Hasher;
+use std::sync::LazyLock;
+
+use rand::SeedableRng;
+
 use crate::*;
 
 use anyhow::Result;
 
+/// Environment variable holding a 64 hex digit seed for `test_rng`.
+const SEED_VAR: &str = "TEST_SEED";
+
+/// Seed shared by every test in the run, read from `TEST_SEED` or sampled.
+static SEED: LazyLock<[u8; 32]> = LazyLock::new(|| match std::env::var(SEED_VAR) {
+    Ok(hex) => {
+        assert_eq!(hex.len(), 64, "{SEED_VAR} must be 64 hex digits");
+        std::array::from_fn(|i| {
+            u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)
+                .unwrap_or_else(|_| panic!("{SEED_VAR} must be 64 hex digits"))
+        })
+    }
+    Err(_) => rand::rng().random(),
+});
+
+thread_local! {
+    /// Number of rngs handed out on this thread, so repeated calls get distinct streams.
+    static RNG_COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Deterministic rng for a test.
+///
+/// Each rng is derived from the run seed with the test name, taken from the name of the thread
+/// libtest runs it on, and the number of earlier calls on that thread. The seed is printed, and
+/// libtest shows it for failing tests, so setting `TEST_SEED` reproduces the failure.
+pub fn test_rng() -> ChaChaRng {
+    let thread = std::thread::current();
+    let name = thread.name().unwrap_or("unnamed");
+    let count = RNG_COUNT.with(|count| count.replace(count.get() + 1));
+    let seed = SEED.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    println!("{name}: test rng {count}, reproduce with {SEED_VAR}={seed}");
+    ChaChaRng::from_seed(*SEED).derive(&format!("{name}/{count}"))
+}
+
+/// A non-cryptographic transcript hasher for tests.
+#[derive(Default, Clone)]
+pub struct TestHasher(DefaultHasher);
+
+impl<E: Element> ElementHasher<E> for TestHasher {
+    fn finish(&self) -> E {
+        E::from(self.0.finish() as u128)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes);
+    }
+}
+
+/// Build an R1CS where each constraint multiplies two of the first `width / 2` variables into
+/// one of the remaining variables, along with a satisfying witness.
+pub fn multiplication_r1cs<E: Element, R: Rng>(
+    height: usize,
+    width: usize,
+    rng: &mut R,
+) -> (R1CS<E>, Vector<E>) {
+    assert!(height <= width / 2);
+    let inputs = width / 2;
+    let mut r1cs = R1CS::identity(height, width);
+    let mut witness = Vector::random(width, rng);
+    for i in 0..height {
+        let left = rng.random_range(0..inputs);
+        let right = rng.random_range(0..inputs);
+        let out = inputs + i;
+        r1cs.a[i][left] = E::one();
+        r1cs.b[i][right] = E::one();
+        r1cs.c[i][out] = E::one();
+        witness[out] = witness[left] * witness[right];
+    }
+    (r1cs, witness)
+}
+
 #[test]
 fn identity_r1cs() -> Result<()> {
     let r1cs = R1CS::<OxfoiScalar>::identity(10, 10);
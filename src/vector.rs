@@ -1,4 +1,12 @@
+use std::iter::Sum;
 use std::ops::IndexMut;
+use std::ops::Range;
+use std::ops::RangeFrom;
+use std::ops::RangeFull;
+use std::ops::RangeInclusive;
+use std::ops::RangeTo;
+
+use anyhow::Result;
 
 use super::*;
 
@@ -53,14 +61,181 @@ impl<E: Element> Vector<E> {
         self.entries.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &E> {
         self.entries.iter()
     }
 
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut E> {
+        self.entries.iter_mut()
+    }
+
     /// Take the entries from `other` and append them to the end of `self`.
     pub fn append(&mut self, mut other: Self) {
         self.entries.append(&mut other.entries);
     }
+
+    /// Get the entries of the vector as a slice.
+    pub fn as_slice(&self) -> &[E] {
+        &self.entries
+    }
+
+    /// Inner product of two vectors of equal length.
+    ///
+    /// Panics if the vectors are not of equal length.
+    pub fn inner(&self, other: &Self) -> E {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "Vector::inner cannot take inner product of vectors of unequal length"
+        );
+        let mut out = E::zero();
+        for (a, b) in self.iter().zip(other.iter()) {
+            out += *a * *b;
+        }
+        out
+    }
+
+    /// Outer product `self * other^T`, a matrix of dimension (self.len(), other.len()).
+    pub fn outer(&self, other: &Self) -> Matrix<E> {
+        let mut out = Matrix::zero(self.len(), other.len());
+        for (i, a) in self.iter().enumerate() {
+            for (out_v, b) in out.row_mut(i).iter_mut().zip(other.iter()) {
+                *out_v = *a * *b;
+            }
+        }
+        out
+    }
+
+    /// Kronecker (tensor) product of two vectors. Entry `i * other.len() + j` is
+    /// `self[i] * other[j]`.
+    pub fn kron(&self, other: &Self) -> Self {
+        let mut entries = Vec::with_capacity(self.len() * other.len());
+        for a in self.iter() {
+            entries.extend(other.iter().map(|b| *a * *b));
+        }
+        Self { entries }
+    }
+
+    /// Split the vector into views of `[0, mid)` and `[mid, len)`.
+    ///
+    /// Panics if `mid > len`.
+    pub fn split_at(&self, mid: usize) -> (&[E], &[E]) {
+        assert!(mid <= self.len(), "Vector::split_at mid outside of vector");
+        self.entries.split_at(mid)
+    }
+
+    /// Iterate over views of `size` entries. The last view may be shorter if `size` does not
+    /// divide the length of the vector.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = &[E]> {
+        self.entries.chunks(size)
+    }
+
+    /// Concatenate a sequence of vectors into a single vector.
+    pub fn concat<I: IntoIterator<Item = Self>>(parts: I) -> Self {
+        let mut out = Self::new(0);
+        for part in parts {
+            out.append(part);
+        }
+        out
+    }
+
+    /// Lift each entry to an integer in the centered representation, see `Element::displacement`.
+    pub fn lift_centered(&self) -> Vec<i128> {
+        self.entries.iter().map(|v| v.displacement()).collect()
+    }
+
+    /// Build a vector from integers in centered representation. Values outside of the field are
+    /// reduced modulo the cardinality.
+    pub fn from_centered(values: &[i128]) -> Self {
+        let cardinality = i128::try_from(E::CARDINALITY).expect("field cardinality exceeds i128");
+        Self {
+            entries: values
+                .iter()
+                .map(|v| E::from(v.rem_euclid(cardinality) as u128))
+                .collect(),
+        }
+    }
+
+    /// Largest displacement of any entry from zero.
+    pub fn norm_inf(&self) -> u128 {
+        self.entries
+            .iter()
+            .map(|v| v.displacement().unsigned_abs())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Sum of the displacements of the entries. Saturates at `u128::MAX`.
+    pub fn norm_l1(&self) -> u128 {
+        self.entries.iter().fold(0u128, |sum, v| {
+            sum.saturating_add(v.displacement().unsigned_abs())
+        })
+    }
+
+    /// Sum of the squared displacements of the entries. Saturates at `u128::MAX`.
+    pub fn norm_l2_squared(&self) -> u128 {
+        self.entries.iter().fold(0u128, |sum, v| {
+            let disp = v.displacement().unsigned_abs();
+            sum.saturating_add(disp.saturating_mul(disp))
+        })
+    }
+
+    /// Euclidean norm of the centered representation.
+    pub fn norm_l2(&self) -> f64 {
+        self.entries
+            .iter()
+            .map(|v| (v.displacement() as f64).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Check that no entry is further than `bound` from zero.
+    pub fn assert_norm_inf(&self, bound: u128) -> Result<()> {
+        for (i, v) in self.entries.iter().enumerate() {
+            let disp = v.displacement();
+            if disp.unsigned_abs() > bound {
+                anyhow::bail!(
+                    "vector contains element {} at index {} beyond infinity norm bound {}",
+                    disp,
+                    i,
+                    bound
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the l1 norm of the vector is at most `bound`.
+    pub fn assert_norm_l1(&self, bound: u128) -> Result<()> {
+        let norm = self.norm_l1();
+        if norm > bound {
+            anyhow::bail!(
+                "vector of length {} has l1 norm {} beyond bound {}",
+                self.len(),
+                norm,
+                bound
+            );
+        }
+        Ok(())
+    }
+
+    /// Check that the squared l2 norm of the vector is at most `bound`.
+    pub fn assert_norm_l2_squared(&self, bound: u128) -> Result<()> {
+        let norm = self.norm_l2_squared();
+        if norm > bound {
+            anyhow::bail!(
+                "vector of length {} has squared l2 norm {} beyond bound {}",
+                self.len(),
+                norm,
+                bound
+            );
+        }
+        Ok(())
+    }
 }
 
 impl<E: Element> Display for Vector<E> {
@@ -90,6 +265,49 @@ impl<E: Element> From<E> for Vector<E> {
     }
 }
 
+impl<E: Element> From<&[E]> for Vector<E> {
+    fn from(value: &[E]) -> Self {
+        Self {
+            entries: value.to_vec(),
+        }
+    }
+}
+
+impl<E: Element> FromIterator<E> for Vector<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Element-wise sum of a sequence of vectors of equal length. The sum of an empty sequence is the
+/// empty vector.
+impl<E: Element> Sum for Vector<E> {
+    fn sum<I: Iterator<Item = Self>>(mut iter: I) -> Self {
+        let Some(mut out) = iter.next() else {
+            return Self::new(0);
+        };
+        for v in iter {
+            out += &v;
+        }
+        out
+    }
+}
+
+impl<'a, E: Element> Sum<&'a Vector<E>> for Vector<E> {
+    fn sum<I: Iterator<Item = &'a Self>>(mut iter: I) -> Self {
+        let Some(first) = iter.next() else {
+            return Self::new(0);
+        };
+        let mut out = first.clone();
+        for v in iter {
+            out += v;
+        }
+        out
+    }
+}
+
 impl<E: Element> IntoIterator for Vector<E> {
     type Item = E;
     type IntoIter = <Vec<E> as IntoIterator>::IntoIter;
@@ -121,6 +339,33 @@ impl<E: Element> IndexMut<usize> for Vector<E> {
     }
 }
 
+macro_rules! impl_range_index {
+    ($($range:ty),*) => {
+        $(
+            impl<E: Element> Index<$range> for Vector<E> {
+                type Output = [E];
+                fn index(&self, index: $range) -> &Self::Output {
+                    &self.entries[index]
+                }
+            }
+
+            impl<E: Element> IndexMut<$range> for Vector<E> {
+                fn index_mut(&mut self, index: $range) -> &mut Self::Output {
+                    &mut self.entries[index]
+                }
+            }
+        )*
+    };
+}
+
+impl_range_index!(
+    Range<usize>,
+    RangeFrom<usize>,
+    RangeTo<usize>,
+    RangeInclusive<usize>,
+    RangeFull
+);
+
 impl<E: Element> Mul<E> for Vector<E> {
     type Output = Self;
     fn mul(mut self, rhs: E) -> Self::Output {
@@ -231,3 +476,155 @@ impl<E: Element> Sub<Vector<E>> for &Vector<E> {
             .into()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn vector_centered_lift() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let values = [0, 1, -1, 7, -7, (Field::CARDINALITY / 2) as i128];
+        let v = Vector::<Field>::from_centered(&values);
+        assert_eq!(v[2], Field::negone());
+        assert_eq!(v.lift_centered(), values);
+
+        // values outside of the field are reduced
+        let cardinality = Field::CARDINALITY as i128;
+        let v = Vector::<Field>::from_centered(&[cardinality + 3, -cardinality - 3]);
+        assert_eq!(v.lift_centered(), [3, -3]);
+
+        let v = Vector::<Field>::random(100, &mut rng);
+        assert_eq!(Vector::from_centered(&v.lift_centered()), v);
+    }
+
+    #[test]
+    fn vector_norms() {
+        type Field = SevenScalar;
+
+        let v = Vector::<Field>::from_centered(&[1, -3, 2, 0, -1]);
+        assert_eq!(v.norm_inf(), 3);
+        assert_eq!(v.norm_l1(), 7);
+        assert_eq!(v.norm_l2_squared(), 15);
+        assert!((v.norm_l2() - 15f64.sqrt()).abs() < 1e-12);
+
+        let zero = Vector::<Field>::new(10);
+        assert_eq!(zero.norm_inf(), 0);
+        assert_eq!(zero.norm_l1(), 0);
+        assert_eq!(zero.norm_l2_squared(), 0);
+        assert_eq!(Vector::<Field>::new(0).norm_inf(), 0);
+    }
+
+    #[test]
+    fn vector_norm_bounds() {
+        type Field = OxfoiScalar;
+
+        let v = Vector::<Field>::from_centered(&[4, -5, 2]);
+        assert!(v.assert_norm_inf(5).is_ok());
+        let err = v.assert_norm_inf(4).unwrap_err().to_string();
+        assert!(err.contains("-5") && err.contains("index 1"), "{err}");
+
+        assert!(v.assert_norm_l1(11).is_ok());
+        assert!(v.assert_norm_l1(10).is_err());
+        assert!(v.assert_norm_l2_squared(45).is_ok());
+        assert!(v.assert_norm_l2_squared(44).is_err());
+    }
+
+    #[test]
+    fn vector_inner_outer() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let a = Vector::<Field>::random(10, &mut rng);
+        let b = Vector::<Field>::random(20, &mut rng);
+        let c = Vector::<Field>::random(10, &mut rng);
+        assert_eq!(a.inner(&c), (a.clone() * &c).into_sum());
+        assert_eq!(a.inner(&c), c.inner(&a));
+
+        let outer = a.outer(&b);
+        assert_eq!(outer.dimension(), (10, 20));
+        for i in 0..10 {
+            for j in 0..20 {
+                assert_eq!(outer[i][j], a[i] * b[j]);
+            }
+        }
+        // (a * b^T) * x = a * <b, x>
+        let x = Vector::random(20, &mut rng);
+        assert_eq!(&outer * &x, a.clone() * b.inner(&x));
+    }
+
+    #[test]
+    fn vector_kron() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let a = Vector::<Field>::random(4, &mut rng);
+        let b = Vector::<Field>::random(7, &mut rng);
+        let k = a.kron(&b);
+        assert_eq!(k.len(), 28);
+        for (i, chunk) in k.chunks(7).enumerate() {
+            assert_eq!(chunk, (b.clone() * a[i]).as_slice());
+        }
+        // <a ⊗ b, c ⊗ d> = <a, c> * <b, d>
+        let c = Vector::<Field>::random(4, &mut rng);
+        let d = Vector::<Field>::random(7, &mut rng);
+        assert_eq!(k.inner(&c.kron(&d)), a.inner(&c) * b.inner(&d));
+    }
+
+    #[test]
+    fn vector_slicing() {
+        type Field = OxfoiScalar;
+        let mut rng = crate::test::test_rng();
+
+        let v = Vector::<Field>::random(10, &mut rng);
+        let (left, right) = v.split_at(4);
+        assert_eq!(left, &v[..4]);
+        assert_eq!(right, &v[4..]);
+        assert_eq!(&v[2..5], &v.as_slice()[2..5]);
+        assert_eq!(&v[2..=5], &v.as_slice()[2..6]);
+        assert_eq!(v[..].len(), 10);
+
+        let chunks = v.chunks(3).collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks[3].len(), 1);
+
+        let parts = v
+            .chunks(3)
+            .map(Vector::from)
+            .collect::<Vec<Vector<Field>>>();
+        assert_eq!(Vector::concat(parts), v);
+
+        let mut w = v.clone();
+        w[..2].copy_from_slice(&[Field::zero(), Field::one()]);
+        assert_eq!(w[1], Field::one());
+        assert_eq!(&w[2..], &v[2..]);
+    }
+
+    #[test]
+    fn vector_collect_and_sum() {
+        type Field = SevenScalar;
+        let mut rng = crate::test::test_rng();
+
+        let v = (0..5u128).map(Field::from).collect::<Vector<Field>>();
+        assert_eq!(v.len(), 5);
+        assert_eq!(v[4], Field::from(4u128));
+
+        let vectors = (0..10)
+            .map(|_| Vector::<Field>::random(6, &mut rng))
+            .collect::<Vec<_>>();
+        let mut expected = Vector::new(6);
+        for v in &vectors {
+            expected += v;
+        }
+        assert_eq!(vectors.iter().sum::<Vector<Field>>(), expected);
+        assert_eq!(vectors.into_iter().sum::<Vector<Field>>(), expected);
+        assert_eq!(
+            std::iter::empty::<Vector<Field>>()
+                .sum::<Vector<Field>>()
+                .len(),
+            0
+        );
+    }
+}